@@ -0,0 +1,229 @@
+//! Times board generation, flood fill and cloning on large boards, next to the same cases on the
+//! vec of rows the board used to be stored as, and the same on a far larger bitboard.
+//!
+//! Run with cargo bench. Each case is repeated a few times and the fastest run is printed, which
+//! is the least affected by whatever else the machine is doing.
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rusty_mine_sweeper::bitboard::BitBoard;
+use rusty_mine_sweeper::{Board, MinesweeperSquare};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const SIZE: usize = 1000;
+const BITBOARD_SIZE: usize = 10_000;
+const RUNS: usize = 5;
+
+/// Runs the setup and then the timed closure RUNS times, returning the fastest time
+fn fastest<S, T>(mut setup: impl FnMut() -> S, mut timed: impl FnMut(S) -> T) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let input = setup();
+            let start = Instant::now();
+            black_box(timed(input));
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+/// The board as it was before its squares moved into one flat vec: a vec of rows of 32 byte
+/// squares, with the neighbours of a square collected into a new vec every time they are needed
+/// and numbers counted as squares are opened. Only what the benchmark times is kept
+mod nested {
+    use super::*;
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        Hidden,
+        Revealed,
+    }
+
+    #[derive(Clone, Copy)]
+    pub struct Square {
+        state: State,
+        value: isize,
+        mines: u8,
+        _flags: u8,
+        _owner: Option<usize>,
+    }
+
+    #[derive(Clone)]
+    pub struct NestedBoard {
+        board: Vec<Vec<Square>>,
+        width: usize,
+        height: usize,
+    }
+
+    impl NestedBoard {
+        pub fn new(width: usize, height: usize) -> NestedBoard {
+            let mut board = Vec::with_capacity(height);
+            for _ in 0..height {
+                let square = Square {
+                    state: State::Hidden,
+                    value: -1,
+                    mines: 0,
+                    _flags: 0,
+                    _owner: None,
+                };
+                board.push(vec![square; width]);
+            }
+            NestedBoard {
+                board,
+                width,
+                height,
+            }
+        }
+
+        pub fn place_mines_seeded(&mut self, count: usize, seed: u64) {
+            let mut rng = StdRng::seed_from_u64(seed);
+            for _ in 0..count.min(self.width * self.height) {
+                loop {
+                    let x = rng.gen_range(0..self.width);
+                    let y = rng.gen_range(0..self.height);
+                    if self.board[y][x].mines == 0 {
+                        self.board[y][x].mines = 1;
+                        break;
+                    }
+                }
+            }
+        }
+
+        fn neighbours(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+            let mut neighbours = Vec::with_capacity(8);
+            for y_offset in -1..=1isize {
+                for x_offset in -1..=1isize {
+                    let (x_index, y_index) = (x as isize + x_offset, y as isize + y_offset);
+                    if (x_offset, y_offset) == (0, 0)
+                        || !(0..self.width as isize).contains(&x_index)
+                        || !(0..self.height as isize).contains(&y_index)
+                    {
+                        continue;
+                    }
+                    neighbours.push((x_index as usize, y_index as usize));
+                }
+            }
+            neighbours
+        }
+
+        /// Opens the square and floods out from it, returning the number of squares opened
+        pub fn reveal(&mut self, x: usize, y: usize) -> usize {
+            let mut revealed = 0;
+            let mut stack = vec![(x, y)];
+            while let Some((x, y)) = stack.pop() {
+                if self.board[y][x].state != State::Hidden || self.board[y][x].mines > 0 {
+                    continue;
+                }
+                self.board[y][x].value = self
+                    .neighbours(x, y)
+                    .into_iter()
+                    .map(|(x_index, y_index)| self.board[y_index][x_index].mines as isize)
+                    .sum();
+                self.board[y][x].state = State::Revealed;
+                revealed += 1;
+                if self.board[y][x].value == 0 {
+                    stack.extend(self.neighbours(x, y));
+                }
+            }
+            revealed
+        }
+    }
+}
+
+fn main() {
+    let baseline: [Duration; 4] = [
+        fastest(|| (), |_| nested::NestedBoard::new(SIZE, SIZE)),
+        fastest(
+            || nested::NestedBoard::new(SIZE, SIZE),
+            |mut board| {
+                board.place_mines_seeded(SIZE * SIZE / 10, 1);
+                board
+            },
+        ),
+        fastest(
+            || nested::NestedBoard::new(SIZE, SIZE),
+            |mut board| board.reveal(0, 0),
+        ),
+        fastest(
+            || {
+                let mut board = nested::NestedBoard::new(SIZE, SIZE);
+                board.place_mines_seeded(SIZE * SIZE / 10, 1);
+                board
+            },
+            |board| board.clone(),
+        ),
+    ];
+    let cases: [(&str, Duration); 4] = [
+        (
+            "new board",
+            fastest(|| (), |_| Board::isize_board(SIZE, SIZE)),
+        ),
+        (
+            "place mines",
+            fastest(
+                || Board::isize_board(SIZE, SIZE),
+                |mut board| {
+                    board.place_mines_seeded(SIZE * SIZE / 10, 1);
+                    board
+                },
+            ),
+        ),
+        (
+            // no mines, so a single reveal opens every square on the board
+            "flood fill",
+            fastest(
+                || Board::isize_board(SIZE, SIZE),
+                |mut board: Board<MinesweeperSquare>| board.reveal(0, 0),
+            ),
+        ),
+        (
+            "clone",
+            fastest(
+                || {
+                    let mut board = Board::isize_board(SIZE, SIZE);
+                    board.place_mines_seeded(SIZE * SIZE / 10, 1);
+                    board
+                },
+                |board| board.clone(),
+            ),
+        ),
+    ];
+    println!("{}x{} board, fastest of {} runs", SIZE, SIZE, RUNS);
+    println!(
+        "{:<12} {:>10} {:>10} {:>8}",
+        "", "vec of rows", "flat", "speedup"
+    );
+    for ((name, time), before) in cases.into_iter().zip(baseline) {
+        println!(
+            "{:<12} {:>11.2?} {:>10.2?} {:>7.1}x",
+            name,
+            before,
+            time,
+            before.as_secs_f64() / time.as_secs_f64()
+        );
+    }
+
+    let new_bitboard = || BitBoard::new(BITBOARD_SIZE, BITBOARD_SIZE).unwrap();
+    let cases: [(&str, Duration); 4] = [
+        ("new board", fastest(|| (), |_| new_bitboard())),
+        (
+            "place mines",
+            fastest(new_bitboard, |mut board| {
+                board.place_mines_seeded(BITBOARD_SIZE * BITBOARD_SIZE / 10, 1);
+                board
+            }),
+        ),
+        (
+            "flood fill",
+            fastest(new_bitboard, |mut board| board.reveal(0, 0)),
+        ),
+        ("clone", fastest(new_bitboard, |board| board.clone())),
+    ];
+    println!(
+        "\n{}x{} bitboard, fastest of {} runs",
+        BITBOARD_SIZE, BITBOARD_SIZE, RUNS
+    );
+    for (name, time) in cases {
+        println!("{:<12} {:>10.2?}", name, time);
+    }
+}
@@ -0,0 +1,280 @@
+use crate::cell::{Cell, CellState};
+use crate::http::{Request, Response};
+use crate::json::Json;
+use crate::session::{Session, SessionManager};
+use crate::view::PlayerView;
+use crate::{Board, Preset, RevealOutcome};
+
+/// Largest width or height of a board created through the API
+pub const MAX_SIZE: usize = 99;
+
+/// Builds a JSON error response
+fn error(status: u16, message: &str) -> Response {
+    Response::json(status, &Json::object([("error", Json::from(message))]))
+}
+
+/// Turns a square into what a player is allowed to see: "hidden", "flagged", "mine" for a
+/// revealed mine, "void" for a square that is not part of a shaped board, or the number on a
+/// revealed square. Hidden mines are never exposed
+fn visible_square(cell: Cell) -> Json {
+    match cell.state {
+        CellState::Hidden => Json::from("hidden"),
+        CellState::Flagged(_) => Json::from("flagged"),
+        CellState::Void => Json::from("void"),
+        CellState::Mine(_) => Json::from("mine"),
+        CellState::Revealed(number) => Json::from(usize::from(number)),
+    }
+}
+
+/// The player visible state of a game
+fn game_state(id: &str, session: &Session) -> Json {
+    let board = &session.board;
+    let view = PlayerView::new(board);
+    let status = if session.lost {
+        "lost"
+    } else if board.is_won().is_some() {
+        "won"
+    } else {
+        "playing"
+    };
+    let flags = view.flagged_cells().count();
+    let cells: Vec<Json> = (0..view.height())
+        .map(|y| {
+            Json::Array(
+                (0..view.width())
+                    .filter_map(|x| view.cell(x, y))
+                    .map(visible_square)
+                    .collect(),
+            )
+        })
+        .collect();
+    let mut fields = vec![
+        ("id", Json::from(id)),
+        ("status", Json::from(status)),
+        ("width", Json::from(view.width())),
+        ("height", Json::from(view.height())),
+        ("mines", Json::from(view.mine_count())),
+        ("flags", Json::from(flags)),
+    ];
+    // The seed would let a client regenerate the mines, so it is only shared once the game is over
+    if session.is_over() {
+        fields.push(("seed", Json::from(session.seed)));
+    }
+    fields.push(("cells", Json::Array(cells)));
+    Json::object(fields)
+}
+
+/// Parses the request body as JSON. An empty body is treated as an empty object
+fn body(request: &Request) -> Result<Json, Response> {
+    let text = String::from_utf8_lossy(&request.body);
+    if text.trim().is_empty() {
+        return Ok(Json::Object(Vec::new()));
+    }
+    Json::parse(&text).map_err(|e| error(400, &format!("Invalid JSON: {}", e)))
+}
+
+/// Reads an optional whole number field from the body
+fn number_field(body: &Json, name: &str) -> Result<Option<u64>, Response> {
+    match body.get(name) {
+        None | Some(Json::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .map(Some)
+            .ok_or_else(|| error(400, &format!("{} must be a whole number", name))),
+    }
+}
+
+/// Creates a new game from {"preset": name} or {"width", "height", "mines"}, with an optional seed
+fn create_game(sessions: &SessionManager, request: &Request) -> Result<Response, Response> {
+    let body = body(request)?;
+    let seed = number_field(&body, "seed")?.unwrap_or_else(rand::random);
+    let (width, height, mines) = match body.get("preset") {
+        Some(name) => name
+            .as_str()
+            .and_then(Preset::from_name)
+            .ok_or_else(|| error(400, "preset must be beginner, intermediate or expert"))?
+            .dimensions(),
+        None => {
+            let (width, height, mines) = (
+                number_field(&body, "width")?.unwrap_or(9) as usize,
+                number_field(&body, "height")?.unwrap_or(9) as usize,
+                number_field(&body, "mines")?,
+            );
+            if !(1..=MAX_SIZE).contains(&width) || !(1..=MAX_SIZE).contains(&height) {
+                return Err(error(
+                    400,
+                    &format!("width and height must be between 1 and {}", MAX_SIZE),
+                ));
+            }
+            let mines = mines.map_or(width * height / 10, |mines| mines as usize);
+            if mines >= width * height {
+                return Err(error(400, "mines must be less than width * height"));
+            }
+            (width, height, mines)
+        }
+    };
+
+    let mut board = Board::isize_board(width, height);
+    board.place_mines_seeded(mines, seed);
+    let id = sessions.create(board, seed).map_err(|e| error(503, e))?;
+    let state = sessions
+        .with_session(&id, |session| game_state(&id, session))
+        .ok_or_else(|| error(500, "Game expired before it could be returned"))?;
+    Ok(Response::json(201, &state))
+}
+
+/// Applies a reveal, flag or chord to the game
+fn make_move(
+    sessions: &SessionManager,
+    id: &str,
+    action: &str,
+    request: &Request,
+) -> Result<Response, Response> {
+    let body = body(request)?;
+    let (x, y) = match (number_field(&body, "x")?, number_field(&body, "y")?) {
+        (Some(x), Some(y)) => (x as usize, y as usize),
+        _ => return Err(error(400, "x and y are required")),
+    };
+    sessions
+        .with_session(id, |session| {
+            if session.is_over() {
+                return Err(error(409, "The game is over"));
+            }
+            if x >= session.board.width || y >= session.board.height {
+                return Err(error(400, "Square selected is out of bounds"));
+            }
+            let outcome = match action {
+                "reveal" => session.board.reveal(x, y),
+                "flag" => session
+                    .board
+                    .toggle_flag(x, y)
+                    .map(|_| RevealOutcome::Revealed(0)),
+                _ => session.board.chord(x, y),
+            };
+            match outcome {
+                Ok(RevealOutcome::Mine) => session.lost = true,
+                Ok(RevealOutcome::Revealed(_)) => {}
+                Err(e) => return Err(error(409, e)),
+            }
+            Ok(Response::json(200, &game_state(id, session)))
+        })
+        .unwrap_or_else(|| Err(error(404, "No game with that id")))
+}
+
+/// Handles a request under /api.
+///
+/// POST /api/games creates a game, GET /api/games/{id} gets its state, DELETE /api/games/{id}
+/// ends it and POST /api/games/{id}/reveal, /flag or /chord with {"x", "y"} makes a move
+pub fn handle(sessions: &SessionManager, request: &Request) -> Response {
+    let parts: Vec<&str> = request
+        .path
+        .trim_start_matches("/api/")
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .collect();
+    let result = match (request.method.as_str(), parts.as_slice()) {
+        ("POST", ["games"]) => create_game(sessions, request),
+        ("GET", ["games", id]) => sessions
+            .with_session(id, |session| Response::json(200, &game_state(id, session)))
+            .ok_or_else(|| error(404, "No game with that id")),
+        ("DELETE", ["games", id]) => match sessions.remove(id) {
+            true => Ok(Response::new(204, "application/json", "")),
+            false => Err(error(404, "No game with that id")),
+        },
+        ("POST", ["games", id, action @ ("reveal" | "flag" | "chord")]) => {
+            make_move(sessions, id, action, request)
+        }
+        (_, ["games"]) | (_, ["games", _]) | (_, ["games", _, "reveal" | "flag" | "chord"]) => {
+            Err(error(405, "Method not allowed"))
+        }
+        _ => Err(error(404, "Not found")),
+    };
+    result.unwrap_or_else(|response| response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str, body: &str) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            body: body.as_bytes().to_vec(),
+            ..Request::default()
+        }
+    }
+
+    fn json(response: &Response) -> Json {
+        Json::parse(&String::from_utf8_lossy(&response.body)).unwrap()
+    }
+
+    #[test]
+    fn test_state_never_exposes_hidden_mines() {
+        let sessions = SessionManager::default();
+        let created = handle(
+            &sessions,
+            &request(
+                "POST",
+                "/api/games",
+                r#"{"width":5,"height":5,"mines":24,"seed":3}"#,
+            ),
+        );
+        assert_eq!(created.status, 201);
+        let state = json(&created);
+        let body = String::from_utf8_lossy(&created.body);
+        assert!(!body.contains("mine\""));
+        assert!(state.get("seed").is_none());
+        assert_eq!(state.get("mines").and_then(Json::as_u64), Some(24));
+
+        // the only safe square is somewhere, find a mine by revealing everything until we lose
+        let id = state.get("id").and_then(Json::as_str).unwrap().to_string();
+        let mut lost = None;
+        'outer: for y in 0..5 {
+            for x in 0..5 {
+                let path = format!("/api/games/{}/reveal", id);
+                let body = format!(r#"{{"x":{},"y":{}}}"#, x, y);
+                let response = handle(&sessions, &request("POST", &path, &body));
+                let state = json(&response);
+                if state.get("status").and_then(Json::as_str) == Some("lost") {
+                    lost = Some(state);
+                    break 'outer;
+                }
+            }
+        }
+        let lost = lost.unwrap();
+        let text = lost.to_string();
+        // only the mine that was hit is shown, the other 23 stay hidden
+        assert_eq!(text.matches("\"mine\"").count(), 1);
+        assert_eq!(lost.get("seed").and_then(Json::as_u64), Some(3));
+    }
+
+    #[test]
+    fn test_errors() {
+        let sessions = SessionManager::default();
+        let bad = [
+            ("POST", "/api/games", r#"{"width":0}"#, 400),
+            (
+                "POST",
+                "/api/games",
+                r#"{"width":3,"height":3,"mines":9}"#,
+                400,
+            ),
+            ("POST", "/api/games", r#"{"preset":"nightmare"}"#, 400),
+            ("POST", "/api/games", "{", 400),
+            ("GET", "/api/games/missing", "", 404),
+            ("POST", "/api/games/missing/reveal", r#"{"x":0,"y":0}"#, 404),
+            ("PUT", "/api/games", "", 405),
+            ("GET", "/api/other", "", 404),
+        ];
+        for (method, path, body, status) in bad {
+            assert_eq!(
+                handle(&sessions, &request(method, path, body)).status,
+                status,
+                "{} {}",
+                method,
+                path
+            );
+        }
+    }
+}
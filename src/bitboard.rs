@@ -0,0 +1,672 @@
+//! Bitboards for very large boards.
+//!
+//! A BitBoard keeps which squares are mines, revealed and flagged as one bit each, packed 64 to
+//! a word with every row starting on a new word. A 10,000x10,000 board then fits in about 40MB.
+//! Numbers are never stored. They are counted when needed for a whole word at once by adding up
+//! the eight shifted neighbour rows with bitwise adders, and openings spread along a row a word
+//! at a time. Only ordinary rectangular boards are supported: bounded edges, the usual 8
+//! neighbours and at most one mine per square.
+use crate::engine::Engine;
+use crate::{Board, MinesweeperSquare, Neighbourhood, RevealOutcome, SquareState, Topology};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::ops::RangeInclusive;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A board of bits for bot simulations on boards far larger than the command line allows.
+/// Plays by the same rules and returns the same errors as Board<MinesweeperSquare>
+pub struct BitBoard {
+    width: usize,
+    height: usize,
+    /// Words in each row
+    words: usize,
+    mines: Vec<u64>,
+    revealed: Vec<u64>,
+    flagged: Vec<u64>,
+    mine_count: usize,
+    safe_revealed: usize,
+}
+
+/// Bit x of the result is bit x - 1 of the row, so each square sees the square to its left
+fn west(row: &[u64], word: usize) -> u64 {
+    let carry = if word > 0 { row[word - 1] >> 63 } else { 0 };
+    (row[word] << 1) | carry
+}
+
+/// Bit x of the result is bit x + 1 of the row, so each square sees the square to its right
+fn east(row: &[u64], word: usize) -> u64 {
+    let carry = row.get(word + 1).map_or(0, |next| next << 63);
+    (row[word] >> 1) | carry
+}
+
+/// Adds one to the count of every square whose bit is set. The count of square x is spread over
+/// the planes, bit x of planes[n] being worth 2^n
+fn add(planes: &mut [u64; 4], mut bits: u64) {
+    for plane in planes.iter_mut() {
+        let carry = *plane & bits;
+        *plane ^= bits;
+        bits = carry;
+    }
+}
+
+/// Spreads the set bits of generate towards higher bits for as long as propagate is set
+fn fill_up(mut generate: u64, mut propagate: u64) -> u64 {
+    for shift in [1, 2, 4, 8, 16, 32] {
+        generate |= propagate & (generate << shift);
+        propagate &= propagate << shift;
+    }
+    generate
+}
+
+/// Spreads the set bits of generate towards lower bits for as long as propagate is set
+fn fill_down(mut generate: u64, mut propagate: u64) -> u64 {
+    for shift in [1, 2, 4, 8, 16, 32] {
+        generate |= propagate & (generate >> shift);
+        propagate &= propagate >> shift;
+    }
+    generate
+}
+
+/// Grows the seeds of a row to cover every run of through bits that a seed is in, carrying the
+/// runs across word boundaries
+fn fill_runs(seeds: &mut [u64], through: &[u64]) {
+    let mut carry = 0;
+    for (seed, &through) in seeds.iter_mut().zip(through) {
+        *seed = fill_up((*seed | carry) & through, through);
+        carry = *seed >> 63;
+    }
+    let mut carry = 0;
+    for (seed, &through) in seeds.iter_mut().zip(through).rev() {
+        *seed = fill_down((*seed | carry) & through, through);
+        carry = (*seed & 1) << 63;
+    }
+}
+
+impl BitBoard {
+    /// Creates a board of hidden squares without any mines
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::bitboard::BitBoard;
+    /// let board = BitBoard::new(10_000, 10_000).unwrap();
+    ///
+    /// assert_eq!((board.width(), board.height()), (10_000, 10_000));
+    /// assert!(BitBoard::new(0, 5).is_err());
+    /// ```
+    pub fn new(width: usize, height: usize) -> Result<BitBoard, &'static str> {
+        if width == 0 || height == 0 {
+            return Err("Boards need at least one row and one column");
+        }
+        let words = width.div_ceil(WORD_BITS);
+        let size = words
+            .checked_mul(height)
+            .ok_or("Board is too large to hold in memory")?;
+        Ok(BitBoard {
+            width,
+            height,
+            words,
+            mines: vec![0; size],
+            revealed: vec![0; size],
+            flagged: vec![0; size],
+            mine_count: 0,
+            safe_revealed: 0,
+        })
+    }
+
+    /// Copies the mines, revealed squares and flags of a board. Fails for boards the bitboard
+    /// cannot represent
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::bitboard::BitBoard;
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare, Topology};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+    /// board.place_mines_seeded(10, 3);
+    ///
+    /// assert_eq!(BitBoard::from_board(&board).unwrap().mine_count(), 10);
+    /// let toroidal: Board<MinesweeperSquare> =
+    ///     Board::isize_board_with_topology(9, 9, Topology::Toroidal);
+    /// assert!(BitBoard::from_board(&toroidal).is_err());
+    /// ```
+    pub fn from_board(board: &Board<MinesweeperSquare>) -> Result<BitBoard, &'static str> {
+        if board.topology() != Topology::Bounded || board.neighbourhood() != Neighbourhood::King {
+            return Err("Bitboards only support bounded boards with the usual 8 neighbours");
+        }
+        let mut bits = BitBoard::new(board.width, board.height)?;
+        for (y, row) in board.iter().enumerate() {
+            for (x, square) in row.iter().enumerate() {
+                let (index, bit) = bits.locate(x, y);
+                match square.mines {
+                    0 => {}
+                    1 => {
+                        bits.mines[index] |= bit;
+                        bits.mine_count += 1;
+                    }
+                    _ => return Err("Bitboards hold at most one mine per square"),
+                }
+                match square.state {
+                    SquareState::Hidden => {}
+                    SquareState::Revealed => {
+                        bits.revealed[index] |= bit;
+                        bits.safe_revealed += usize::from(!square.is_mine());
+                    }
+                    SquareState::Flagged => bits.flagged[index] |= bit,
+                    SquareState::Void => return Err("Bitboards must be rectangles"),
+                }
+            }
+        }
+        Ok(bits)
+    }
+
+    /// Number of columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of mines on the board
+    pub fn mine_count(&self) -> usize {
+        self.mine_count
+    }
+
+    /// Places or removes a mine on the square. Out of bounds cords are ignored
+    pub fn set_mine(&mut self, x: usize, y: usize, is_mine: bool) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let (index, bit) = self.locate(x, y);
+        if (self.mines[index] & bit != 0) != is_mine {
+            self.mines[index] ^= bit;
+            if is_mine {
+                self.mine_count += 1;
+            } else {
+                self.mine_count -= 1;
+            }
+        }
+    }
+
+    /// Returns true if the square holds a mine
+    pub fn is_mine(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.bit(&self.mines, x, y)
+    }
+
+    /// Returns true if the square has been revealed
+    pub fn is_revealed(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.bit(&self.revealed, x, y)
+    }
+
+    /// Randomly places the given number of mines on squares without one. Stops early if the
+    /// board runs out of free squares
+    pub fn place_mines(&mut self, count: usize) {
+        self.place_mines_with_rng(count, &mut rand::thread_rng());
+    }
+
+    /// Places the given number of mines using a random number generator seeded with the seed
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::bitboard::BitBoard;
+    /// let mut first = BitBoard::new(200, 100).unwrap();
+    /// let mut second = BitBoard::new(200, 100).unwrap();
+    /// first.place_mines_seeded(2_000, 42);
+    /// second.place_mines_seeded(2_000, 42);
+    ///
+    /// assert_eq!(first.mine_count(), 2_000);
+    /// assert_eq!(first, second);
+    /// ```
+    pub fn place_mines_seeded(&mut self, count: usize, seed: u64) {
+        self.place_mines_with_rng(count, &mut StdRng::seed_from_u64(seed));
+    }
+
+    fn place_mines_with_rng<R: Rng>(&mut self, count: usize, rng: &mut R) {
+        let free_space = self.width * self.height - self.mine_count;
+        for _ in 0..count.min(free_space) {
+            loop {
+                let (x, y) = (rng.gen_range(0..self.width), rng.gen_range(0..self.height));
+                if !self.bit(&self.mines, x, y) {
+                    self.set_mine(x, y, true);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Reveals the square, opening up the squares around it if it has no mines next to it.
+    /// Returns RevealOutcome::Mine if it was a mine, otherwise the number of squares opened
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::bitboard::BitBoard;
+    /// use rusty_mine_sweeper::RevealOutcome;
+    /// let mut board = BitBoard::new(300, 200).unwrap();
+    /// board.set_mine(299, 199, true);
+    ///
+    /// assert_eq!(board.reveal(0, 0), Ok(RevealOutcome::Revealed(300 * 200 - 1)));
+    /// assert_eq!(board.number(298, 198), Some(1));
+    /// assert!(board.is_won().is_some());
+    /// ```
+    pub fn reveal(&mut self, x: usize, y: usize) -> Result<RevealOutcome, &'static str> {
+        if x >= self.width || y >= self.height {
+            return Err("Square selected is out of bounds");
+        }
+        if self.bit(&self.revealed, x, y) {
+            return Err("Square has already been revealed");
+        }
+        if self.bit(&self.flagged, x, y) {
+            return Err("Square is flagged. Remove the flag first");
+        }
+        if self.bit(&self.mines, x, y) {
+            self.set_bit(x, y);
+            return Ok(RevealOutcome::Mine);
+        }
+        let revealed = self.open(x, y);
+        self.flag_remaining_mines();
+        Ok(RevealOutcome::Revealed(revealed))
+    }
+
+    /// Places or removes a flag on the square. Returns true if the square is now flagged
+    pub fn toggle_flag(&mut self, x: usize, y: usize) -> Result<bool, &'static str> {
+        if x >= self.width || y >= self.height {
+            return Err("Square selected is out of bounds");
+        }
+        if self.bit(&self.revealed, x, y) {
+            return Err("Square has already been revealed");
+        }
+        let (index, bit) = self.locate(x, y);
+        self.flagged[index] ^= bit;
+        Ok(self.flagged[index] & bit != 0)
+    }
+
+    /// Chords on a revealed number, revealing its other hidden neighbours once the flags around
+    /// it match the number
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::bitboard::BitBoard;
+    /// use rusty_mine_sweeper::RevealOutcome;
+    /// let mut board = BitBoard::new(3, 1).unwrap();
+    /// board.set_mine(0, 0, true);
+    ///
+    /// board.reveal(1, 0).unwrap();
+    /// assert!(board.chord(1, 0).is_err());
+    /// board.toggle_flag(0, 0).unwrap();
+    /// assert_eq!(board.chord(1, 0), Ok(RevealOutcome::Revealed(1)));
+    /// ```
+    pub fn chord(&mut self, x: usize, y: usize) -> Result<RevealOutcome, &'static str> {
+        if x >= self.width || y >= self.height {
+            return Err("Square selected is out of bounds");
+        }
+        if !self.bit(&self.revealed, x, y) {
+            return Err("Only revealed squares can be chorded");
+        }
+        let number = self.number(x, y).map(usize::from);
+        let mut flags = 0;
+        let mut hidden = Vec::new();
+        for (x_index, y_index) in self.neighbours(x, y) {
+            if self.bit(&self.flagged, x_index, y_index) {
+                flags += 1;
+            } else if !self.bit(&self.revealed, x_index, y_index) {
+                hidden.push((x_index, y_index));
+            }
+        }
+        if number != Some(flags) {
+            return Err("Number of flags around the square does not match its number");
+        }
+        if hidden.is_empty() {
+            return Err("No hidden squares around the square to reveal");
+        }
+
+        let mut revealed = 0;
+        let mut hit_mine = false;
+        for (x_index, y_index) in hidden {
+            if self.bit(&self.mines, x_index, y_index) {
+                self.set_bit(x_index, y_index);
+                hit_mine = true;
+            } else if !self.bit(&self.revealed, x_index, y_index) {
+                revealed += self.open(x_index, y_index);
+            }
+        }
+        if hit_mine {
+            return Ok(RevealOutcome::Mine);
+        }
+        self.flag_remaining_mines();
+        Ok(RevealOutcome::Revealed(revealed))
+    }
+
+    /// Returns the number on a revealed square, None if the square is hidden, flagged or a mine
+    pub fn number(&self, x: usize, y: usize) -> Option<u8> {
+        if !self.is_revealed(x, y) || self.bit(&self.mines, x, y) {
+            return None;
+        }
+        let planes = self.counts(y, x / WORD_BITS);
+        let shift = x % WORD_BITS;
+        Some(
+            planes
+                .iter()
+                .enumerate()
+                .map(|(n, plane)| (((plane >> shift) & 1) as u8) << n)
+                .sum(),
+        )
+    }
+
+    /// Returns true if the square has a flag on it
+    pub fn is_flagged(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.bit(&self.flagged, x, y)
+    }
+
+    /// Returns Some(()) once every mine is flagged and no safe square is
+    pub fn is_won(&self) -> Option<()> {
+        (self.mines == self.flagged).then_some(())
+    }
+
+    /// Returns true once every square that is not a mine has been revealed
+    pub fn is_cleared(&self) -> bool {
+        self.safe_revealed == self.width * self.height - self.mine_count
+    }
+
+    /// Returns the word index and bit of the square
+    fn locate(&self, x: usize, y: usize) -> (usize, u64) {
+        (y * self.words + x / WORD_BITS, 1 << (x % WORD_BITS))
+    }
+
+    fn bit(&self, bits: &[u64], x: usize, y: usize) -> bool {
+        let (index, bit) = self.locate(x, y);
+        bits[index] & bit != 0
+    }
+
+    /// Marks the square as revealed
+    fn set_bit(&mut self, x: usize, y: usize) {
+        let (index, bit) = self.locate(x, y);
+        self.revealed[index] |= bit;
+    }
+
+    /// The words of row y of the bits
+    fn row<'a>(&self, bits: &'a [u64], y: usize) -> &'a [u64] {
+        &bits[y * self.words..(y + 1) * self.words]
+    }
+
+    /// The rows touching row y
+    fn rows_around(&self, y: usize) -> RangeInclusive<usize> {
+        y.saturating_sub(1)..=(y + 1).min(self.height - 1)
+    }
+
+    /// Bits of the squares that are on the board in a word, as the last word of a row is usually
+    /// only partly used
+    fn valid(&self, word: usize) -> u64 {
+        let used = self.width - word * WORD_BITS;
+        if used >= WORD_BITS {
+            u64::MAX
+        } else {
+            (1 << used) - 1
+        }
+    }
+
+    fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let columns = x.saturating_sub(1)..=(x + 1).min(self.width - 1);
+        self.rows_around(y)
+            .flat_map(move |y_index| columns.clone().map(move |x_index| (x_index, y_index)))
+            .filter(move |&cord| cord != (x, y))
+    }
+
+    /// Counts the mines around each of the 64 squares of a word in row y, returned as bit planes
+    /// as in add
+    fn counts(&self, y: usize, word: usize) -> [u64; 4] {
+        let mut planes = [0; 4];
+        for y_index in self.rows_around(y) {
+            let row = self.row(&self.mines, y_index);
+            add(&mut planes, west(row, word));
+            add(&mut planes, east(row, word));
+            if y_index != y {
+                add(&mut planes, row[word]);
+            }
+        }
+        planes
+    }
+
+    /// The squares of row y with no mine in or around them
+    fn zeros(&self, y: usize) -> Vec<u64> {
+        let mines = self.row(&self.mines, y);
+        (0..self.words)
+            .map(|word| {
+                let [ones, twos, fours, eights] = self.counts(y, word);
+                !(ones | twos | fours | eights) & !mines[word] & self.valid(word)
+            })
+            .collect()
+    }
+
+    /// Reveals a hidden safe square and any opening it starts. Returns the number of squares
+    /// revealed
+    fn open(&mut self, x: usize, y: usize) -> usize {
+        self.set_bit(x, y);
+        self.safe_revealed += 1;
+        if self
+            .counts(y, x / WORD_BITS)
+            .iter()
+            .any(|plane| plane >> (x % WORD_BITS) & 1 != 0)
+        {
+            return 1;
+        }
+
+        // rows are revisited whenever a row next to them gains a square with no mines around it
+        let mut revealed = 1;
+        let mut queued = vec![false; self.height];
+        let mut rows: Vec<usize> = self.rows_around(y).collect();
+        for &row in rows.iter() {
+            queued[row] = true;
+        }
+        while let Some(y) = rows.pop() {
+            queued[y] = false;
+            let (opened, new_zeros) = self.open_row(y);
+            revealed += opened;
+            if new_zeros {
+                for row in self.rows_around(y) {
+                    if !queued[row] {
+                        queued[row] = true;
+                        rows.push(row);
+                    }
+                }
+            }
+        }
+        self.safe_revealed += revealed - 1;
+        revealed
+    }
+
+    /// Reveals every hidden square of row y that an opening reaches: squares next to a revealed
+    /// zero in the rows around it, and then along the row through any zeros those reach.
+    /// Returns the number of squares revealed and whether any of them were zeros
+    fn open_row(&mut self, y: usize) -> (usize, bool) {
+        let zeros = self.zeros(y);
+        let mut next_to_zero = vec![0; self.words];
+        for y_index in self.rows_around(y) {
+            let sources: Vec<u64> = if y_index == y {
+                zeros.clone()
+            } else {
+                self.zeros(y_index)
+            };
+            let sources: Vec<u64> = sources
+                .iter()
+                .zip(self.row(&self.revealed, y_index))
+                .map(|(zero, revealed)| zero & revealed)
+                .collect();
+            for (word, next) in next_to_zero.iter_mut().enumerate() {
+                *next |= sources[word] | west(&sources, word) | east(&sources, word);
+            }
+        }
+
+        let start = y * self.words;
+        let flagged = self.row(&self.flagged, y);
+        let revealed = self.row(&self.revealed, y);
+        let through: Vec<u64> = zeros
+            .iter()
+            .zip(flagged)
+            .map(|(zero, flagged)| zero & !flagged)
+            .collect();
+        let mut runs: Vec<u64> = (0..self.words)
+            .map(|word| (next_to_zero[word] | revealed[word]) & through[word])
+            .collect();
+        fill_runs(&mut runs, &through);
+
+        let mut opened = 0;
+        let mut new_zeros = false;
+        for word in 0..self.words {
+            let reached = next_to_zero[word] | runs[word] | west(&runs, word) | east(&runs, word);
+            let index = start + word;
+            let open = reached
+                & !self.revealed[index]
+                & !self.flagged[index]
+                & !self.mines[index]
+                & self.valid(word);
+            self.revealed[index] |= open;
+            opened += open.count_ones() as usize;
+            new_zeros |= open & zeros[word] != 0;
+        }
+        (opened, new_zeros)
+    }
+
+    /// Flags every mine once all of the safe squares have been revealed, as Board does
+    fn flag_remaining_mines(&mut self) {
+        if !self.is_cleared() {
+            return;
+        }
+        for (flagged, mines) in self.flagged.iter_mut().zip(&self.mines) {
+            *flagged |= mines;
+        }
+    }
+}
+
+impl Engine for BitBoard {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn mine_count(&self) -> usize {
+        self.mine_count
+    }
+
+    fn reveal(&mut self, x: usize, y: usize) -> Result<RevealOutcome, &'static str> {
+        BitBoard::reveal(self, x, y)
+    }
+
+    fn toggle_flag(&mut self, x: usize, y: usize) -> Result<bool, &'static str> {
+        BitBoard::toggle_flag(self, x, y)
+    }
+
+    fn chord(&mut self, x: usize, y: usize) -> Result<RevealOutcome, &'static str> {
+        BitBoard::chord(self, x, y)
+    }
+
+    fn number(&self, x: usize, y: usize) -> Option<u8> {
+        BitBoard::number(self, x, y)
+    }
+
+    fn is_flagged(&self, x: usize, y: usize) -> bool {
+        BitBoard::is_flagged(self, x, y)
+    }
+
+    fn is_won(&self) -> Option<()> {
+        BitBoard::is_won(self)
+    }
+
+    fn is_cleared(&self) -> bool {
+        BitBoard::is_cleared(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::seq::SliceRandom;
+
+    /// Plays the same moves on a board and its bitboard copy and checks they agree throughout
+    fn play_both(width: usize, height: usize, mines: usize, seed: u64) {
+        let mut board: Board<MinesweeperSquare> = Board::isize_board(width, height);
+        board.place_mines_seeded(mines, seed);
+        let mut bits = BitBoard::from_board(&board).unwrap();
+        let mut safe: Vec<(usize, usize)> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| !board[(x, y)].is_mine())
+            .collect();
+        safe.shuffle(&mut StdRng::seed_from_u64(seed));
+
+        for (x, y) in safe {
+            assert_eq!(
+                bits.reveal(x, y),
+                board.reveal(x, y),
+                "reveal ({}, {})",
+                x,
+                y
+            );
+            assert_eq!(bits.is_cleared(), board.is_cleared());
+        }
+        for y in 0..height {
+            for x in 0..width {
+                assert_eq!(Engine::number(&bits, x, y), Engine::number(&board, x, y));
+                assert_eq!(bits.is_flagged(x, y), Engine::is_flagged(&board, x, y));
+            }
+        }
+        assert_eq!(bits.is_won(), board.is_won());
+    }
+
+    #[test]
+    fn test_matches_board() {
+        play_both(9, 9, 10, 1);
+        play_both(30, 16, 99, 2);
+        // several words to a row, and a last word that is only partly used
+        play_both(150, 40, 600, 3);
+        play_both(128, 20, 100, 4);
+    }
+
+    #[test]
+    fn test_chord_and_errors_match_board() {
+        let mut board: Board<MinesweeperSquare> = Board::isize_board(70, 3);
+        board.place_mines_seeded(40, 5);
+        let mut bits = BitBoard::from_board(&board).unwrap();
+        let mine = (0..70).find(|&x| board[(x, 1)].is_mine()).unwrap();
+        let number = (0..70)
+            .find(|&x| !board[(x, 1)].is_mine() && x.abs_diff(mine) == 1)
+            .unwrap();
+
+        assert_eq!(bits.reveal(70, 0), board.reveal(70, 0));
+        assert_eq!(bits.reveal(number, 1), board.reveal(number, 1));
+        assert_eq!(bits.reveal(number, 1), board.reveal(number, 1));
+        assert_eq!(bits.chord(number, 1), board.chord(number, 1));
+        assert_eq!(bits.toggle_flag(mine, 1), board.toggle_flag(mine, 1));
+        assert_eq!(bits.reveal(mine, 1), board.reveal(mine, 1));
+        assert_eq!(bits.chord(number, 1), board.chord(number, 1));
+        assert_eq!(
+            bits.reveal(mine, 1).is_err(),
+            board.reveal(mine, 1).is_err()
+        );
+    }
+
+    #[test]
+    fn test_opens_a_large_board_at_once() {
+        let mut bits = BitBoard::new(2_000, 1_000).unwrap();
+        bits.set_mine(1_000, 500, true);
+        assert_eq!(
+            bits.reveal(0, 0),
+            Ok(RevealOutcome::Revealed(2_000 * 1_000 - 1))
+        );
+        assert_eq!(bits.number(999, 499), Some(1));
+        assert_eq!(bits.number(998, 499), Some(0));
+        assert!(bits.is_cleared());
+    }
+
+    #[test]
+    fn test_flagging_everything_does_not_win() {
+        let mut bits = BitBoard::new(3, 1).unwrap();
+        bits.set_mine(0, 0, true);
+        for x in 0..3 {
+            bits.toggle_flag(x, 0).unwrap();
+        }
+        assert!(bits.is_won().is_none());
+        bits.toggle_flag(1, 0).unwrap();
+        bits.toggle_flag(2, 0).unwrap();
+        assert!(bits.is_won().is_some());
+    }
+}
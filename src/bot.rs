@@ -0,0 +1,332 @@
+use crate::solver;
+use crate::view::PlayerView;
+use crate::{Board, MinesweeperSquare, RevealOutcome};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What the bot wants to do to a square
+pub enum BotAction {
+    Reveal,
+    Flag,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A single move picked by a strategy. guess is true if the strategy could not prove the move safe
+pub struct BotMove {
+    pub action: BotAction,
+    pub x: usize,
+    pub y: usize,
+    pub guess: bool,
+}
+
+/// A way of picking moves. Strategies are only given what a player could see on the board:
+/// the state of each square, the numbers on revealed squares and the total number of mines
+pub trait Strategy {
+    /// Short name used in the benchmark output
+    fn name(&self) -> &'static str;
+
+    /// Picks the next move for the board. Returns None if there is nothing left to do
+    fn next_move(&mut self, view: &PlayerView, rng: &mut StdRng) -> Option<BotMove>;
+}
+
+/// Returns the cords of every hidden square that has not been flagged
+fn hidden_squares(view: &PlayerView) -> Vec<(usize, usize)> {
+    view.hidden_cells().map(|cell| (cell.x, cell.y)).collect()
+}
+
+/// Picks a random hidden square to reveal
+fn random_guess(view: &PlayerView, rng: &mut StdRng) -> Option<BotMove> {
+    hidden_squares(view).choose(rng).map(|&(x, y)| BotMove {
+        action: BotAction::Reveal,
+        x,
+        y,
+        guess: true,
+    })
+}
+
+/// Returns the first move that can be proven from the revealed numbers, preferring reveals
+fn deduced_move(view: &PlayerView) -> Option<BotMove> {
+    let deductions = solver::deduce(view);
+    let reveal = deductions.safe.iter().next().map(|&(x, y)| BotMove {
+        action: BotAction::Reveal,
+        x,
+        y,
+        guess: false,
+    });
+    reveal.or_else(|| {
+        deductions.mines.iter().next().map(|&(x, y)| BotMove {
+            action: BotAction::Flag,
+            x,
+            y,
+            guess: false,
+        })
+    })
+}
+
+/// Reveals random hidden squares. Mostly useful as a baseline
+pub struct RandomStrategy;
+
+impl Strategy for RandomStrategy {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn next_move(&mut self, view: &PlayerView, rng: &mut StdRng) -> Option<BotMove> {
+        random_guess(view, rng)
+    }
+}
+
+/// Plays every move that can be proven and guesses at random when stuck
+pub struct DeductionStrategy;
+
+impl Strategy for DeductionStrategy {
+    fn name(&self) -> &'static str {
+        "deduction"
+    }
+
+    fn next_move(&mut self, view: &PlayerView, rng: &mut StdRng) -> Option<BotMove> {
+        deduced_move(view).or_else(|| random_guess(view, rng))
+    }
+}
+
+/// Plays every move that can be proven and otherwise reveals the square least likely to be a mine
+pub struct ProbabilityStrategy;
+
+impl Strategy for ProbabilityStrategy {
+    fn name(&self) -> &'static str {
+        "probability"
+    }
+
+    fn next_move(&mut self, view: &PlayerView, rng: &mut StdRng) -> Option<BotMove> {
+        if let Some(next) = deduced_move(view) {
+            return Some(next);
+        }
+        let chances = solver::mine_probabilities(view);
+        let lowest = chances
+            .iter()
+            .map(|(_, chance)| *chance)
+            .fold(f64::INFINITY, f64::min);
+        let best: Vec<(usize, usize)> = chances
+            .into_iter()
+            .filter(|(_, chance)| *chance <= lowest)
+            .map(|(cord, _)| cord)
+            .collect();
+        best.choose(rng).map(|&(x, y)| BotMove {
+            action: BotAction::Reveal,
+            x,
+            y,
+            guess: true,
+        })
+    }
+}
+
+/// Returns the strategy with the given name
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::bot::strategy_by_name;
+///
+/// assert_eq!(strategy_by_name("deduction").unwrap().name(), "deduction");
+/// assert!(strategy_by_name("cheating").is_none());
+/// ```
+pub fn strategy_by_name(name: &str) -> Option<Box<dyn Strategy>> {
+    match name {
+        "random" => Some(Box::new(RandomStrategy)),
+        "deduction" => Some(Box::new(DeductionStrategy)),
+        "probability" => Some(Box::new(ProbabilityStrategy)),
+        _ => None,
+    }
+}
+
+/// Names of every built in strategy
+pub const STRATEGY_NAMES: [&str; 3] = ["random", "deduction", "probability"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Result of a single game played by a bot
+pub struct GameResult {
+    pub won: bool,
+    pub guesses: usize,
+    pub moves: usize,
+}
+
+/// Plays the board to the end with the strategy, showing it a fresh PlayerView before each move
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::bot::{play, DeductionStrategy};
+/// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// let mut board: Board<MinesweeperSquare> = Board::isize_board(5, 5);
+///
+/// let result = play(&mut board, &mut DeductionStrategy, &mut StdRng::seed_from_u64(1));
+/// assert!(result.won);
+/// ```
+pub fn play(
+    board: &mut Board<MinesweeperSquare>,
+    strategy: &mut dyn Strategy,
+    rng: &mut StdRng,
+) -> GameResult {
+    let mut result = GameResult {
+        won: false,
+        guesses: 0,
+        moves: 0,
+    };
+    while board.is_won().is_none() {
+        let Some(next) = strategy.next_move(&PlayerView::new(board), rng) else {
+            break;
+        };
+        result.moves += 1;
+        if next.guess {
+            result.guesses += 1;
+        }
+        let outcome = match next.action {
+            BotAction::Reveal => board.reveal(next.x, next.y),
+            BotAction::Flag => board
+                .toggle_flag(next.x, next.y)
+                .map(|_| RevealOutcome::Revealed(0)),
+        };
+        match outcome {
+            Ok(RevealOutcome::Mine) => return result,
+            Ok(RevealOutcome::Revealed(_)) => {}
+            // A strategy asking for an invalid move would otherwise loop forever
+            Err(_) => return result,
+        }
+    }
+    result.won = board.is_won().is_some();
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Board configuration and number of games for a benchmark run
+pub struct BenchmarkConfig {
+    pub width: usize,
+    pub height: usize,
+    pub mines: usize,
+    pub games: usize,
+    /// Seed of the first game. Game n uses seed + n so every strategy plays the same boards
+    pub seed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Totals from playing many games with one strategy
+pub struct BenchmarkResult {
+    pub strategy: &'static str,
+    pub config: BenchmarkConfig,
+    pub wins: usize,
+    pub guesses: usize,
+    pub elapsed: Duration,
+}
+
+impl BenchmarkResult {
+    /// Percentage of games won
+    pub fn win_rate(&self) -> f64 {
+        if self.config.games == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.config.games as f64 * 100.0
+    }
+
+    /// Average number of guesses made per game
+    pub fn average_guesses(&self) -> f64 {
+        if self.config.games == 0 {
+            return 0.0;
+        }
+        self.guesses as f64 / self.config.games as f64
+    }
+}
+
+/// Displays the result as a single row of the benchmark table
+impl fmt::Display for BenchmarkResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let board = format!(
+            "{}x{}/{}",
+            self.config.width, self.config.height, self.config.mines
+        );
+        write!(
+            f,
+            "{:<12} {:<11} {:>6} {:>7.2}% {:>8.2} {:>9.2}s",
+            board,
+            self.strategy,
+            self.config.games,
+            self.win_rate(),
+            self.average_guesses(),
+            self.elapsed.as_secs_f64()
+        )
+    }
+}
+
+/// Header matching the rows displayed by BenchmarkResult
+pub const BENCHMARK_HEADER: &str = "board        strategy     games win rate  guesses   runtime";
+
+/// Plays config.games games with the strategy and totals the results. Boards and the strategy's
+/// random choices are seeded so runs can be repeated exactly
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::bot::{benchmark, BenchmarkConfig, ProbabilityStrategy};
+/// let config = BenchmarkConfig { width: 9, height: 9, mines: 10, games: 20, seed: 3 };
+/// let result = benchmark(&mut ProbabilityStrategy, config);
+///
+/// assert!(result.wins <= 20);
+/// assert!(result.guesses >= 20);
+/// ```
+pub fn benchmark(strategy: &mut dyn Strategy, config: BenchmarkConfig) -> BenchmarkResult {
+    let started = Instant::now();
+    let mut wins = 0;
+    let mut guesses = 0;
+    for game in 0..config.games {
+        let seed = config.seed.wrapping_add(game as u64);
+        let mut board = Board::isize_board(config.width, config.height);
+        board.place_mines_seeded(config.mines, seed);
+        let result = play(&mut board, strategy, &mut StdRng::seed_from_u64(seed));
+        if result.won {
+            wins += 1;
+        }
+        guesses += result.guesses;
+    }
+    BenchmarkResult {
+        strategy: strategy.name(),
+        config,
+        wins,
+        guesses,
+        elapsed: started.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deduction_beats_random() {
+        let config = BenchmarkConfig {
+            width: 9,
+            height: 9,
+            mines: 10,
+            games: 200,
+            seed: 11,
+        };
+        let random = benchmark(&mut RandomStrategy, config);
+        let deduction = benchmark(&mut DeductionStrategy, config);
+        let probability = benchmark(&mut ProbabilityStrategy, config);
+        assert!(deduction.wins > random.wins);
+        assert!(probability.wins >= deduction.wins);
+        assert!(deduction.average_guesses() < random.average_guesses());
+    }
+
+    #[test]
+    fn test_benchmark_is_repeatable() {
+        let config = BenchmarkConfig {
+            width: 8,
+            height: 8,
+            mines: 10,
+            games: 50,
+            seed: 5,
+        };
+        let first = benchmark(&mut ProbabilityStrategy, config);
+        let second = benchmark(&mut ProbabilityStrategy, config);
+        assert_eq!((first.wins, first.guesses), (second.wins, second.guesses));
+    }
+}
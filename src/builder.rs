@@ -0,0 +1,326 @@
+//! Building boards from checked settings.
+//!
+//! Board::isize_board and place_mines accept anything, so a board with no squares or more mines
+//! than squares is only noticed once it is played. BoardBuilder checks the settings first and
+//! says what is wrong with them, and can hold back where the mines go until the first click.
+use crate::{mix, Board, MinesweeperSquare, Neighbourhood, Topology};
+use std::error::Error;
+use std::fmt;
+
+/// Most squares a built board can have, so a mistyped size is an error rather than an attempt to
+/// allocate more memory than there is
+pub const MAX_SQUARES: usize = 1 << 24;
+
+/// What the first square revealed on a board is allowed to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FirstClick {
+    /// The mines stay where they were placed, so the first click can lose the game
+    #[default]
+    Anywhere,
+    /// A mine under the first click is moved somewhere else
+    Safe,
+    /// The first click and its neighbours are cleared of mines, so it always opens an area
+    Opening,
+}
+
+impl FirstClick {
+    /// Squares kept clear of mines when (x, y) is the first square revealed
+    pub(crate) fn keep_clear(
+        self,
+        board: &Board<MinesweeperSquare>,
+        x: usize,
+        y: usize,
+    ) -> Vec<(usize, usize)> {
+        match self {
+            FirstClick::Anywhere => Vec::new(),
+            FirstClick::Safe => vec![(x, y)],
+            FirstClick::Opening => board.neighbours(x, y).chain([(x, y)]).collect(),
+        }
+    }
+
+    /// Most squares keep_clear can return on a board with the neighbourhood
+    fn reserved(self, neighbourhood: Neighbourhood) -> usize {
+        match self {
+            FirstClick::Anywhere => 0,
+            FirstClick::Safe => 1,
+            FirstClick::Opening => {
+                neighbourhood
+                    .offsets(0)
+                    .len()
+                    .max(neighbourhood.offsets(1).len())
+                    + 1
+            }
+        }
+    }
+}
+
+/// Why a board could not be built
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError {
+    /// The width or height is 0
+    EmptyBoard,
+    /// The board would have more than MAX_SQUARES squares
+    TooLarge { width: usize, height: usize },
+    /// More mines were asked for than the board can hold under the first click policy
+    TooManyMines { mines: usize, room: usize },
+    /// The density is not a fraction from 0 to 1
+    InvalidDensity(f64),
+    /// Hex boards only wrap around when the shifted rows line up, which needs an even height
+    OddWrappedHexHeight(usize),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::EmptyBoard => write!(f, "The board needs at least one row and column"),
+            BuildError::TooLarge { width, height } => {
+                write!(f, "A {}x{} board is too large", width, height)
+            }
+            BuildError::TooManyMines { mines, room } => write!(
+                f,
+                "{} mines were asked for but the board only has room for {}",
+                mines, room
+            ),
+            BuildError::InvalidDensity(density) => {
+                write!(f, "Mine density {} is not between 0 and 1", density)
+            }
+            BuildError::OddWrappedHexHeight(height) => {
+                write!(f, "Wrapped hex boards need an even height, not {}", height)
+            }
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// How many mines to place
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mines {
+    Count(usize),
+    Density(f64),
+}
+
+/// Settings for a new board, checked when the board is built
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::builder::{BoardBuilder, BuildError, FirstClick};
+/// use rusty_mine_sweeper::{RevealOutcome, Topology};
+/// let mut board = BoardBuilder::new(16, 16)
+///     .mines(40)
+///     .seed(7)
+///     .topology(Topology::Toroidal)
+///     .first_click(FirstClick::Opening)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(board.mine_count(), 40);
+/// assert!(matches!(board.reveal(5, 5), Ok(RevealOutcome::Revealed(_))));
+/// assert_eq!(board.mines_around(5, 5), Some(0));
+///
+/// assert_eq!(
+///     BoardBuilder::new(3, 3).mines(9).first_click(FirstClick::Safe).build(),
+///     Err(BuildError::TooManyMines { mines: 9, room: 8 })
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardBuilder {
+    width: usize,
+    height: usize,
+    mines: Mines,
+    seed: Option<u64>,
+    topology: Topology,
+    neighbourhood: Neighbourhood,
+    first_click: FirstClick,
+}
+
+impl BoardBuilder {
+    /// Starts a board of the given size with no mines, ordinary edges and neighbours, and mines
+    /// allowed under the first click
+    pub fn new(width: usize, height: usize) -> BoardBuilder {
+        BoardBuilder {
+            width,
+            height,
+            mines: Mines::Count(0),
+            seed: None,
+            topology: Topology::Bounded,
+            neighbourhood: Neighbourhood::King,
+            first_click: FirstClick::Anywhere,
+        }
+    }
+
+    /// Places exactly this many mines
+    pub fn mines(mut self, mines: usize) -> BoardBuilder {
+        self.mines = Mines::Count(mines);
+        self
+    }
+
+    /// Covers this fraction of the squares in mines, rounded to the nearest mine
+    pub fn density(mut self, density: f64) -> BoardBuilder {
+        self.mines = Mines::Density(density);
+        self
+    }
+
+    /// Places the mines, and moves them for the first click, from this seed so the same
+    /// settings always give the same game
+    pub fn seed(mut self, seed: u64) -> BoardBuilder {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets how the edges of the board behave
+    pub fn topology(mut self, topology: Topology) -> BoardBuilder {
+        self.topology = topology;
+        self
+    }
+
+    /// Sets which squares count as neighbours
+    pub fn neighbourhood(mut self, neighbourhood: Neighbourhood) -> BoardBuilder {
+        self.neighbourhood = neighbourhood;
+        self
+    }
+
+    /// Sets what the first square revealed is allowed to be
+    pub fn first_click(mut self, first_click: FirstClick) -> BoardBuilder {
+        self.first_click = first_click;
+        self
+    }
+
+    /// Checks the settings and builds the board with its mines placed
+    pub fn build(&self) -> Result<Board<MinesweeperSquare>, BuildError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(BuildError::EmptyBoard);
+        }
+        let squares = self
+            .width
+            .checked_mul(self.height)
+            .filter(|&squares| squares <= MAX_SQUARES)
+            .ok_or(BuildError::TooLarge {
+                width: self.width,
+                height: self.height,
+            })?;
+        if self.neighbourhood == Neighbourhood::Hex
+            && self.topology == Topology::Toroidal
+            && self.height % 2 == 1
+        {
+            return Err(BuildError::OddWrappedHexHeight(self.height));
+        }
+        let mines = match self.mines {
+            Mines::Count(mines) => mines,
+            Mines::Density(density) if (0.0..=1.0).contains(&density) => {
+                (squares as f64 * density).round() as usize
+            }
+            Mines::Density(density) => return Err(BuildError::InvalidDensity(density)),
+        };
+        let room = squares.saturating_sub(self.first_click.reserved(self.neighbourhood));
+        if mines > room {
+            return Err(BuildError::TooManyMines { mines, room });
+        }
+
+        let mut board = Board::isize_board_with_neighbourhood(
+            self.width,
+            self.height,
+            self.topology,
+            self.neighbourhood,
+        );
+        let relocation_seed = match self.seed {
+            Some(seed) => {
+                board.place_mines_seeded(mines, seed);
+                mix(seed)
+            }
+            None => {
+                board.place_mines(mines);
+                rand::random()
+            }
+        };
+        if self.first_click != FirstClick::Anywhere {
+            board.first_click = Some((self.first_click, relocation_seed));
+        }
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RevealOutcome;
+
+    #[test]
+    fn test_rejects_bad_settings() {
+        assert_eq!(BoardBuilder::new(0, 5).build(), Err(BuildError::EmptyBoard));
+        assert!(matches!(
+            BoardBuilder::new(usize::MAX, 2).build(),
+            Err(BuildError::TooLarge { .. })
+        ));
+        assert_eq!(
+            BoardBuilder::new(1 << 20, 1 << 20).build(),
+            Err(BuildError::TooLarge {
+                width: 1 << 20,
+                height: 1 << 20
+            })
+        );
+        assert!(matches!(
+            BoardBuilder::new(9, 9).density(1.5).build(),
+            Err(BuildError::InvalidDensity(_))
+        ));
+        assert_eq!(
+            BoardBuilder::new(4, 4)
+                .mines(8)
+                .first_click(FirstClick::Opening)
+                .build(),
+            Err(BuildError::TooManyMines { mines: 8, room: 7 })
+        );
+        assert_eq!(
+            BoardBuilder::new(6, 5)
+                .topology(Topology::Toroidal)
+                .neighbourhood(Neighbourhood::Hex)
+                .build(),
+            Err(BuildError::OddWrappedHexHeight(5))
+        );
+    }
+
+    #[test]
+    fn test_first_click_policies() {
+        for y in 0..9 {
+            for x in 0..9 {
+                let builder = BoardBuilder::new(9, 9).mines(72).seed(3);
+                let mut safe = builder
+                    .clone()
+                    .first_click(FirstClick::Safe)
+                    .build()
+                    .unwrap();
+                assert!(matches!(safe.reveal(x, y), Ok(RevealOutcome::Revealed(_))));
+                assert_eq!(safe.mine_count(), 72);
+
+                let mut opening = builder
+                    .mines(40)
+                    .first_click(FirstClick::Opening)
+                    .build()
+                    .unwrap();
+                assert!(opening.reveal(x, y).is_ok());
+                assert_eq!(opening.mines_around(x, y), Some(0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_tall_boards_render() {
+        for neighbourhood in [Neighbourhood::King, Neighbourhood::Hex] {
+            let board = BoardBuilder::new(2, 300)
+                .neighbourhood(neighbourhood)
+                .build()
+                .unwrap();
+            let text = board.to_string();
+            assert!(text.contains("\naa "));
+            assert!(text.contains("\nkn "));
+            assert!(text.contains("\na  "));
+        }
+    }
+
+    #[test]
+    fn test_seed_and_density() {
+        let builder = BoardBuilder::new(30, 16).density(0.2).seed(11);
+        let board = builder.build().unwrap();
+        assert_eq!(board.mine_count(), 96);
+        assert_eq!(board, builder.build().unwrap());
+    }
+}
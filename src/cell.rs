@@ -0,0 +1,113 @@
+//! Read-only views of the squares of a board.
+//!
+//! Front ends draw a board from these rather than from MinesweeperSquare, so they can tell
+//! hidden, flagged and revealed squares apart without reaching into the engine. A cell only
+//! says what is under a square once the square has been revealed.
+use crate::{Board, MinesweeperSquare, SquareState};
+
+/// What can be seen of a square
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellState {
+    /// Not part of a shaped board
+    Void,
+    /// Not revealed yet
+    Hidden,
+    /// Not revealed, with this many flags on it
+    Flagged(u8),
+    /// Revealed safe square showing the number of mines around it
+    Revealed(u8),
+    /// Revealed square holding this many mines
+    Mine(u8),
+}
+
+/// A square of the board and its cords
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub x: usize,
+    pub y: usize,
+    pub state: CellState,
+    /// Player who claimed the mine on this square in Minesweeper Flags, see Board::claim
+    pub owner: Option<u8>,
+}
+
+impl Cell {
+    /// The view of the square at (x, y)
+    pub(crate) fn new(board: &Board<MinesweeperSquare>, x: usize, y: usize) -> Cell {
+        let square = &board[(x, y)];
+        let state = match square.state {
+            SquareState::Void => CellState::Void,
+            SquareState::Hidden => CellState::Hidden,
+            SquareState::Flagged => CellState::Flagged(square.flags),
+            SquareState::Revealed if square.is_mine() => CellState::Mine(square.mines),
+            SquareState::Revealed => CellState::Revealed(square.count),
+        };
+        Cell {
+            x,
+            y,
+            state,
+            owner: square.owner,
+        }
+    }
+
+    /// Returns true if the square is part of the board and has not been revealed or flagged
+    pub fn is_hidden(&self) -> bool {
+        self.state == CellState::Hidden
+    }
+
+    /// Returns true if the square has at least one flag on it
+    pub fn is_flagged(&self) -> bool {
+        matches!(self.state, CellState::Flagged(_))
+    }
+
+    /// Returns the number on a revealed safe square
+    pub fn number(&self) -> Option<u8> {
+        match self.state {
+            CellState::Revealed(number) => Some(number),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout;
+
+    #[test]
+    fn test_cells_show_only_what_was_revealed() {
+        let mut board = layout::parse("*..\n.F-\n...").unwrap();
+        board.reveal(2, 2).unwrap();
+        let states: Vec<CellState> = board.cells().map(|cell| cell.state).collect();
+        assert_eq!(
+            states,
+            [
+                CellState::Hidden,
+                CellState::Hidden,
+                CellState::Hidden,
+                CellState::Hidden,
+                CellState::Flagged(1),
+                CellState::Void,
+                CellState::Hidden,
+                CellState::Hidden,
+                CellState::Revealed(1),
+            ]
+        );
+        assert_eq!(board.hidden_cells().count(), 6);
+        assert_eq!(
+            board.flagged_cells().map(|cell| (cell.x, cell.y)).next(),
+            Some((1, 1))
+        );
+    }
+
+    #[test]
+    fn test_neighbour_cells_leave_out_void_squares() {
+        let board = layout::parse("1*.\n---\n...").unwrap();
+        let neighbours: Vec<(usize, usize)> = board
+            .neighbour_cells(1, 0)
+            .map(|cell| (cell.x, cell.y))
+            .collect();
+        assert_eq!(neighbours, [(0, 0), (2, 0)]);
+        assert_eq!(board.cell(0, 0).and_then(|cell| cell.number()), Some(1));
+        assert_eq!(board.cell(3, 0), None);
+    }
+}
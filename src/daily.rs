@@ -0,0 +1,78 @@
+//! Daily challenge.
+//!
+//! Every day has its own board, generated from a seed worked out from the date alone, so everyone
+//! playing on the same day gets the same mines. The seed only depends on the date, which keeps
+//! past challenges playable and means a challenge can be checked against anyone else's.
+use crate::date::Date;
+use crate::{mix, Board, MinesweeperSquare, Preset};
+
+/// Board size of every daily challenge
+pub const PRESET: Preset = Preset::Intermediate;
+/// Statistics category daily challenges are recorded under, apart from the preset's own
+pub const CATEGORY: &str = "daily";
+/// Mixed into the date so the daily boards are not the same as boards played with small seeds
+const SALT: u64 = 0x6461_696c_7973_6565;
+
+/// Seed of the challenge for the date
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::daily;
+/// use rusty_mine_sweeper::date::Date;
+/// let date = Date { year: 2024, month: 5, day: 1 };
+/// let next = Date { year: 2024, month: 5, day: 2 };
+///
+/// assert_eq!(daily::seed(date), daily::seed(date));
+/// assert_ne!(daily::seed(date), daily::seed(next));
+/// ```
+pub fn seed(date: Date) -> u64 {
+    mix(SALT ^ date.days_since_epoch() as u64)
+}
+
+/// Board of the challenge for the date, with its mines placed
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::daily;
+/// use rusty_mine_sweeper::date::Date;
+/// let board = daily::board(Date { year: 2024, month: 5, day: 1 });
+///
+/// assert_eq!((board.width, board.height), (16, 16));
+/// assert_eq!(board.mine_count(), 40);
+/// ```
+pub fn board(date: Date) -> Board<MinesweeperSquare> {
+    PRESET.board_with_seed(seed(date))
+}
+
+/// Checks that the challenge for the date can be played, as the challenges of days that have not
+/// happened yet are kept secret until then
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::daily;
+/// use rusty_mine_sweeper::date::Date;
+/// let today = Date { year: 2024, month: 5, day: 1 };
+///
+/// assert!(daily::check_date(Date { year: 2024, month: 4, day: 30 }, today).is_ok());
+/// assert!(daily::check_date(Date { year: 2024, month: 5, day: 2 }, today).is_err());
+/// ```
+pub fn check_date(date: Date, today: Date) -> Result<(), &'static str> {
+    if date > today {
+        return Err("That day's challenge is not out yet");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_date_same_mines() {
+        let date = Date {
+            year: 2023,
+            month: 12,
+            day: 31,
+        };
+        assert_eq!(board(date), board(date));
+        let next = Date::from_days_since_epoch(date.days_since_epoch() + 1);
+        assert_ne!(board(date), board(next));
+    }
+}
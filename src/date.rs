@@ -0,0 +1,137 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// A calendar date (UTC) in the proleptic gregorian calendar
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Today's date in UTC
+    pub fn today() -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        Date::from_days_since_epoch((seconds / SECONDS_PER_DAY) as i64)
+    }
+
+    /// Converts the number of days since 1970-01-01 into a date
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::date::Date;
+    ///
+    /// assert_eq!(Date::from_days_since_epoch(0).to_string(), "1970-01-01");
+    /// assert_eq!(Date::from_days_since_epoch(19_782).to_string(), "2024-02-29");
+    /// ```
+    pub fn from_days_since_epoch(days: i64) -> Date {
+        // Howard Hinnant's civil_from_days algorithm
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Date { year, month, day }
+    }
+
+    /// Number of days between 1970-01-01 and the date
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::date::Date;
+    /// let date = Date { year: 2024, month: 2, day: 29 };
+    ///
+    /// assert_eq!(date.days_since_epoch(), 19_782);
+    /// ```
+    pub fn days_since_epoch(&self) -> i64 {
+        // Howard Hinnant's days_from_civil algorithm
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let mp = if month > 2 { month - 3 } else { month + 9 };
+        let doy = (153 * mp + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+}
+
+/// Displays the date as YYYY-MM-DD
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Parses a date written as YYYY-MM-DD, with a minus sign in front of years before 0
+impl FromStr for Date {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Date, Self::Err> {
+        let s = s.trim();
+        // a year before 0 starts with its own minus sign, so the year ends at the next one
+        let (year, rest) = match s.get(1..).and_then(|tail| tail.find('-')) {
+            Some(end) => (&s[..=end], &s[end + 2..]),
+            None => return Err("Dates must be written as YYYY-MM-DD"),
+        };
+        let mut parts = rest.splitn(2, '-');
+        let mut next = || parts.next().and_then(|part| part.parse().ok());
+        let (year, month, day) = match (year.parse::<i32>(), next(), next()) {
+            (Ok(year), Some(month), Some(day)) => (year, month, day),
+            (Err(_), Some(_), Some(_)) if year.trim_start_matches('-').parse::<u64>().is_ok() => {
+                return Err("Year is out of range")
+            }
+            _ => return Err("Dates must be written as YYYY-MM-DD"),
+        };
+        let date = Date { year, month, day };
+        // Round trip through the day count to reject dates such as 2023-02-30
+        if month == 0 || day == 0 || Date::from_days_since_epoch(date.days_since_epoch()) != date {
+            return Err("Date does not exist");
+        }
+        Ok(date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trip() {
+        let date: Date = "2023-12-31".parse().unwrap();
+        assert_eq!(date.to_string(), "2023-12-31");
+        assert_eq!(
+            Date::from_days_since_epoch(date.days_since_epoch() + 1).to_string(),
+            "2024-01-01"
+        );
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("2023-13-01".parse::<Date>().is_err());
+        assert!("yesterday".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn test_years_outside_i32_are_rejected() {
+        let date: Date = "-0044-03-15".parse().unwrap();
+        assert_eq!(date.year, -44);
+        assert_eq!(Date::from_days_since_epoch(date.days_since_epoch()), date);
+        let last: Date = "2147483647-12-31".parse().unwrap();
+        assert_eq!(last.year, i32::MAX);
+        assert_eq!(
+            "2147483648-01-01".parse::<Date>(),
+            Err("Year is out of range")
+        );
+        assert_eq!(
+            "4294967295-01-01".parse::<Date>(),
+            Err("Year is out of range")
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! The moves and game state shared by every board representation.
+//!
+//! Board<MinesweeperSquare> is the full engine with every topology, neighbourhood and mine
+//! count. BitBoard only supports ordinary rectangular boards but is small enough to simulate
+//! boards far larger than the command line allows. Code written against Engine runs on either.
+use crate::{Board, MinesweeperSquare, RevealOutcome, SquareState};
+
+/// A board that can be played. Cords are (x, y) from the top left square and every move returns
+/// the same errors as Board<MinesweeperSquare>
+pub trait Engine {
+    /// Number of columns
+    fn width(&self) -> usize;
+
+    /// Number of rows
+    fn height(&self) -> usize;
+
+    /// Number of mines on the board
+    fn mine_count(&self) -> usize;
+
+    /// Reveals a hidden square, opening up the squares around it if it has no mines next to it
+    fn reveal(&mut self, x: usize, y: usize) -> Result<RevealOutcome, &'static str>;
+
+    /// Places or removes a flag. Returns true if the square is now flagged
+    fn toggle_flag(&mut self, x: usize, y: usize) -> Result<bool, &'static str>;
+
+    /// Reveals the hidden neighbours of a number once the flags around it match the number
+    fn chord(&mut self, x: usize, y: usize) -> Result<RevealOutcome, &'static str>;
+
+    /// Returns the number on a revealed square, None if the square is not a revealed number
+    fn number(&self, x: usize, y: usize) -> Option<u8>;
+
+    /// Returns true if the square has a flag on it
+    fn is_flagged(&self, x: usize, y: usize) -> bool;
+
+    /// Returns Some(()) once every mine is flagged and no safe square is
+    fn is_won(&self) -> Option<()>;
+
+    /// Returns true once every square that is not a mine has been revealed
+    fn is_cleared(&self) -> bool;
+}
+
+/// The full engine. Every method is the inherent method of the same name
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::engine::Engine;
+/// use rusty_mine_sweeper::{Board, MinesweeperSquare, RevealOutcome};
+///
+/// fn open_corner(engine: &mut impl Engine) -> Result<RevealOutcome, &'static str> {
+///     engine.reveal(0, 0)
+/// }
+///
+/// let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 3);
+/// assert_eq!(open_corner(&mut board), Ok(RevealOutcome::Revealed(9)));
+/// assert_eq!(Engine::number(&board, 1, 1), Some(0));
+/// ```
+impl Engine for Board<MinesweeperSquare> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn mine_count(&self) -> usize {
+        Board::mine_count(self)
+    }
+
+    fn reveal(&mut self, x: usize, y: usize) -> Result<RevealOutcome, &'static str> {
+        Board::reveal(self, x, y)
+    }
+
+    fn toggle_flag(&mut self, x: usize, y: usize) -> Result<bool, &'static str> {
+        Board::toggle_flag(self, x, y)
+    }
+
+    fn chord(&mut self, x: usize, y: usize) -> Result<RevealOutcome, &'static str> {
+        Board::chord(self, x, y)
+    }
+
+    fn number(&self, x: usize, y: usize) -> Option<u8> {
+        let square = self.get(x, y)?;
+        if square.state != SquareState::Revealed || square.is_mine() {
+            return None;
+        }
+        u8::try_from(square.value).ok()
+    }
+
+    fn is_flagged(&self, x: usize, y: usize) -> bool {
+        self.get(x, y)
+            .is_some_and(|square| square.state == SquareState::Flagged)
+    }
+
+    fn is_won(&self) -> Option<()> {
+        Board::is_won(self)
+    }
+
+    fn is_cleared(&self) -> bool {
+        Board::is_cleared(self)
+    }
+}
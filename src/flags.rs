@@ -0,0 +1,111 @@
+use crate::{Board, MinesweeperSquare, RevealOutcome};
+
+/// Board size of the classic Minesweeper Flags game. The odd mine count means there can be no draw
+pub const CLASSIC_SIZE: (usize, usize, usize) = (16, 16, 51);
+
+/// What happened on a turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TurnOutcome {
+    /// The player claimed a mine and takes another turn
+    Claimed,
+    /// The player opened up some squares and the turn passes to the other player
+    Revealed(usize),
+}
+
+/// A two player game of Minesweeper Flags. Players take turns revealing squares on one shared
+/// board, finding a mine scores a point and grants another turn, and the first to claim more
+/// than half of the mines wins
+pub struct FlagsGame {
+    pub board: Board<MinesweeperSquare>,
+    scores: [usize; 2],
+    turn: usize,
+}
+
+impl FlagsGame {
+    /// Starts a game on the board, with the first player to move
+    pub fn new(board: Board<MinesweeperSquare>) -> FlagsGame {
+        FlagsGame {
+            board,
+            scores: [0, 0],
+            turn: 0,
+        }
+    }
+
+    /// Index of the player whose turn it is, 0 or 1
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    /// Mines claimed by each player
+    pub fn scores(&self) -> [usize; 2] {
+        self.scores
+    }
+
+    /// Reveals the square at (x, y) for the player whose turn it is
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::flags::{FlagsGame, TurnOutcome};
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 1);
+    /// let mut mine = MinesweeperSquare::new();
+    /// mine.set_is_mine(true);
+    /// board.set(0, 0, mine);
+    /// let mut game = FlagsGame::new(board);
+    ///
+    /// assert_eq!(game.take_turn(2, 0), Ok(TurnOutcome::Revealed(2)));
+    /// assert_eq!(game.turn(), 1);
+    /// assert_eq!(game.take_turn(0, 0), Ok(TurnOutcome::Claimed));
+    /// assert_eq!(game.scores(), [0, 1]);
+    /// assert_eq!(game.winner(), Some(1));
+    /// ```
+    pub fn take_turn(&mut self, x: usize, y: usize) -> Result<TurnOutcome, &'static str> {
+        if self.is_over() {
+            return Err("The game is over");
+        }
+        match self.board.claim(x, y, self.turn)? {
+            RevealOutcome::Mine => {
+                self.scores[self.turn] += 1;
+                Ok(TurnOutcome::Claimed)
+            }
+            RevealOutcome::Revealed(revealed) => {
+                self.turn = 1 - self.turn;
+                Ok(TurnOutcome::Revealed(revealed))
+            }
+        }
+    }
+
+    /// Returns the player that has claimed more than half of the mines, if either has
+    pub fn winner(&self) -> Option<usize> {
+        let mines = self.board.mine_count();
+        (0..2).find(|&player| self.scores[player] * 2 > mines)
+    }
+
+    /// Returns true once a player has won or every mine has been claimed, which on a board with an
+    /// even number of mines can end in a draw
+    pub fn is_over(&self) -> bool {
+        self.winner().is_some() || self.scores[0] + self.scores[1] == self.board.mine_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_even_mines_can_draw() {
+        let mut board: Board<MinesweeperSquare> = Board::isize_board(4, 1);
+        let mut mine = MinesweeperSquare::new();
+        mine.set_is_mine(true);
+        board.set(0, 0, mine);
+        board.set(3, 0, mine);
+        let mut game = FlagsGame::new(board);
+
+        assert_eq!(game.take_turn(0, 0), Ok(TurnOutcome::Claimed));
+        assert_eq!(game.take_turn(1, 0), Ok(TurnOutcome::Revealed(1)));
+        assert_eq!(game.take_turn(3, 0), Ok(TurnOutcome::Claimed));
+        assert!(game.is_over());
+        assert_eq!(game.winner(), None);
+        assert_eq!(game.scores(), [1, 1]);
+        assert!(game.take_turn(2, 0).is_err());
+    }
+}
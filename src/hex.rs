@@ -0,0 +1,190 @@
+//! Hexagonal boards.
+//!
+//! Hex boards use "odd-r" offset coordinates: squares keep their usual (x, y) or row letter and
+//! column number, but every odd row (b, d, f, ...) is shifted half a cell to the right. A cell
+//! touches the two cells beside it in its own row and two cells in each of the rows above and
+//! below. Which two depends on the row: even rows reach up and down to the left, odd rows to the
+//! right. The renderer draws the shift so the neighbours can be seen, with the column numbers of
+//! even rows above the board and those of odd rows below it. A cell is typed as one cord, its
+//! row letters then the column number read on that row's side of the board, such as "b3".
+use crate::{
+    parse_row_label, row_label, write_row_label, write_square, Board, MinesweeperSquare, Topology,
+};
+use std::fmt;
+
+/// Offsets to the neighbours of a cell in an even row
+pub const EVEN_ROW_OFFSETS: &[(isize, isize)] =
+    &[(-1, 0), (1, 0), (-1, -1), (0, -1), (-1, 1), (0, 1)];
+/// Offsets to the neighbours of a cell in an odd row, which is shifted half a cell to the right
+pub const ODD_ROW_OFFSETS: &[(isize, isize)] = &[(-1, 0), (1, 0), (0, -1), (1, -1), (0, 1), (1, 1)];
+
+/// Prompt for the cord of a cell on a hex board
+pub const CORD_PROMPT: &str = "Enter the cell's row letters then column number, such as 'b3': ";
+
+/// Parses a hex cord such as "b3" or "b 3", the row letters then the column number shown on the
+/// row's side of the board, into (row, column) indexes on a board of the given size
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::hex::parse_cord;
+///
+/// assert_eq!(parse_cord("b3", 5, 4), Ok((1, 2)));
+/// assert_eq!(parse_cord(" ab 1 ", 5, 30), Ok((27, 0)));
+/// assert!(parse_cord("b", 5, 4).is_err());
+/// assert!(parse_cord("3b", 5, 4).is_err());
+/// assert!(parse_cord("e1", 5, 4).is_err());
+/// ```
+pub fn parse_cord(
+    cord: &str,
+    max_width: usize,
+    max_height: usize,
+) -> Result<(usize, usize), &'static str> {
+    let cord = cord.trim();
+    let split = cord
+        .find(|c: char| !c.is_ascii_lowercase())
+        .unwrap_or(cord.len());
+    let (row, col) = cord.split_at(split);
+    let Some(row_index) = parse_row_label(row) else {
+        return Err("Invalid cell selection. Please start with the row's letters, such as 'b3'.");
+    };
+    let col_index = match col.trim_start().parse::<usize>() {
+        Ok(num) if num > 0 => num - 1,
+        _ => {
+            return Err("Invalid cell selection. Please end with the column number, such as 'b3'.")
+        }
+    };
+
+    if row_index >= max_height {
+        Err("Row selected is out of bounds")
+    } else if col_index >= max_width {
+        Err("Column selected is out of bounds")
+    } else {
+        Ok((row_index, col_index))
+    }
+}
+
+/// Writes the column numbers, indented to line up with the cells of rows that are shifted by
+/// the indent
+fn write_columns(
+    f: &mut fmt::Formatter,
+    board: &Board<MinesweeperSquare>,
+    indent: &str,
+) -> fmt::Result {
+    let label_width = row_label(board.height.saturating_sub(1)).len();
+    write!(f, "{:width$} {}", "", indent, width = label_width)?;
+    for i in 0..board.width {
+        write!(f, "{:<4}", i + 1)?;
+    }
+    writeln!(f)
+}
+
+/// Renders a hex board with every odd row shifted half a cell to the right
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::{Board, MinesweeperSquare, Neighbourhood, Topology};
+/// let board: Board<MinesweeperSquare> =
+///     Board::isize_board_with_neighbourhood(3, 2, Topology::Bounded, Neighbourhood::Hex);
+/// let text = board.to_string();
+/// let lines: Vec<&str> = text.lines().collect();
+///
+/// assert_eq!(lines[0].trim_end(), "  1   2   3");
+/// assert!(lines[1].starts_with("a \u{25FB}   \u{25FB}"));
+/// assert!(lines[2].starts_with("b   \u{25FB}   \u{25FB}"));
+/// assert_eq!(lines[3].trim_end(), "    1   2   3");
+/// ```
+pub fn render(board: &Board<MinesweeperSquare>, f: &mut fmt::Formatter) -> fmt::Result {
+    write_columns(f, board, "")?;
+    for (y, row) in board.iter().enumerate() {
+        write_row_label(f, y, board.height)?;
+        if y % 2 == 1 {
+            write!(f, "  ")?;
+        }
+        for (x, square) in row.iter().enumerate() {
+            if x > 0 {
+                write!(f, "   ")?;
+            }
+            write_square(f, square)?;
+        }
+        writeln!(f)?;
+    }
+    if board.height > 1 {
+        write_columns(f, board, "  ")?;
+    }
+    if board.topology() == Topology::Toroidal {
+        writeln!(f, "~ edges wrap around to the opposite side")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Neighbourhood, RevealOutcome};
+
+    fn hex_board(width: usize, height: usize) -> Board<MinesweeperSquare> {
+        Board::isize_board_with_neighbourhood(width, height, Topology::Bounded, Neighbourhood::Hex)
+    }
+
+    #[test]
+    fn test_neighbours_are_symmetric() {
+        let board = hex_board(5, 6);
+        for y in 0..6 {
+            for x in 0..5 {
+                let neighbours: Vec<_> = board.neighbours(x, y).collect();
+                assert!(neighbours.len() <= 6);
+                for (nx, ny) in neighbours {
+                    assert!(board.neighbours(nx, ny).any(|cord| cord == (x, y)));
+                }
+            }
+        }
+        assert_eq!(board.neighbours(2, 2).count(), 6);
+        assert_eq!(board.neighbours(2, 3).count(), 6);
+    }
+
+    #[test]
+    fn test_reveal_counts_six_neighbours() {
+        let mut board = hex_board(3, 3);
+        let mut mine = MinesweeperSquare::new();
+        mine.set_is_mine(true);
+        // (0, 0) is not a neighbour of (1, 1) on a hex board, but (2, 0) is
+        board.set(0, 0, mine);
+        board.reveal(1, 1).unwrap();
+        assert_eq!(board.get(1, 1).unwrap().get_value(), 0);
+
+        let mut board = hex_board(3, 3);
+        board.set(2, 0, mine);
+        assert_eq!(board.reveal(1, 1), Ok(RevealOutcome::Revealed(1)));
+        assert_eq!(board.get(1, 1).unwrap().get_value(), 1);
+    }
+
+    #[test]
+    fn test_wrapped_odd_boards_get_an_even_height() {
+        let board =
+            Board::isize_board_with_neighbourhood(5, 5, Topology::Toroidal, Neighbourhood::Hex);
+        assert_eq!(board.height, 6);
+        for y in 0..board.height {
+            for x in 0..board.width {
+                assert_eq!(board.neighbours(x, y).count(), 6);
+                for (nx, ny) in board.neighbours(x, y) {
+                    assert!(board.neighbours(nx, ny).any(|cord| cord == (x, y)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cords_name_the_drawn_cells() {
+        let mut board = hex_board(3, 2);
+        let (row, col) = parse_cord("b2", board.width, board.height).unwrap();
+        board.cycle_flag(col, row).unwrap();
+        // the flag is drawn in the shifted row b, under the 2 of the bottom column numbers
+        let text = board.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        let flag = lines[2].chars().position(|c| c == '\u{1F6A9}');
+        assert_eq!(lines[3].chars().position(|c| c == '2'), flag);
+        assert_eq!(
+            parse_cord("c1", board.width, board.height),
+            Err("Row selected is out of bounds")
+        );
+        assert!(parse_cord("b-1", board.width, board.height).is_err());
+    }
+}
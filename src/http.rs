@@ -0,0 +1,283 @@
+use crate::json::Json;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Largest request head (request line and headers) that will be read
+const MAX_HEAD_SIZE: usize = 8 * 1024;
+/// Largest request body that will be read
+const MAX_BODY_SIZE: usize = 64 * 1024;
+/// How long a connection may sit idle before it is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// A parsed HTTP/1.1 request
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Reads a single request from the stream
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::http::Request;
+    /// let raw = "POST /move?mode=flag HTTP/1.1\r\nContent-Length: 10\r\n\r\ncell=1%2C2";
+    /// let request = Request::read(&mut raw.as_bytes()).unwrap();
+    ///
+    /// assert_eq!(request.method, "POST");
+    /// assert_eq!(request.path, "/move");
+    /// assert_eq!(request.query["mode"], "flag");
+    /// assert_eq!(request.form()["cell"], "1,2");
+    /// ```
+    pub fn read(stream: &mut impl BufRead) -> io::Result<Request> {
+        let invalid =
+            |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+        let mut head_size = 0;
+        let mut read_line = |stream: &mut dyn BufRead| -> io::Result<String> {
+            let mut line = String::new();
+            let read = stream
+                .take((MAX_HEAD_SIZE - head_size) as u64)
+                .read_line(&mut line)?;
+            head_size += read;
+            if read == 0 || !line.ends_with('\n') {
+                return Err(invalid("request head is incomplete or too large"));
+            }
+            Ok(line.trim_end().to_string())
+        };
+
+        let request_line = read_line(stream)?;
+        let mut parts = request_line.split_whitespace();
+        let (method, target) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(method), Some(target), Some(version)) if version.starts_with("HTTP/1.") => {
+                (method.to_string(), target.to_string())
+            }
+            _ => return Err(invalid("malformed request line")),
+        };
+
+        let mut headers = HashMap::new();
+        loop {
+            let line = read_line(stream)?;
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| invalid("malformed header"))?;
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+
+        let length = match headers.get("content-length") {
+            Some(length) => length
+                .parse::<usize>()
+                .map_err(|_| invalid("invalid content length"))?,
+            None => 0,
+        };
+        if length > MAX_BODY_SIZE {
+            return Err(invalid("request body is too large"));
+        }
+        let mut body = vec![0; length];
+        stream.read_exact(&mut body)?;
+
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), parse_urlencoded(query)),
+            None => (target, HashMap::new()),
+        };
+        Ok(Request {
+            method,
+            path,
+            query,
+            headers,
+            body,
+        })
+    }
+
+    /// Parses the body as an application/x-www-form-urlencoded form
+    pub fn form(&self) -> HashMap<String, String> {
+        parse_urlencoded(&String::from_utf8_lossy(&self.body))
+    }
+}
+
+/// Decodes a percent encoded string, turning + into a space
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::http::url_decode;
+///
+/// assert_eq!(url_decode("a+b%21"), "a b!");
+/// assert_eq!(url_decode("%+1%zz"), "% 1%zz");
+/// ```
+pub fn url_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            // from_str_radix would also take a sign, so both characters are checked first
+            b'%' if i + 2 < bytes.len()
+                && bytes[i + 1].is_ascii_hexdigit()
+                && bytes[i + 2].is_ascii_hexdigit() =>
+            {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                decoded.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                i += 2;
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Parses key=value pairs separated by &
+pub fn parse_urlencoded(s: &str) -> HashMap<String, String> {
+    s.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (url_decode(key), url_decode(value)),
+            None => (url_decode(pair), String::new()),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A HTTP response to be written back to the client
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// Creates a response with the given status, content type and body
+    pub fn new(status: u16, content_type: &str, body: impl Into<Vec<u8>>) -> Response {
+        Response {
+            status,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body: body.into(),
+        }
+    }
+
+    /// 200 OK response with a HTML body
+    pub fn html(body: impl Into<Vec<u8>>) -> Response {
+        Response::new(200, "text/html; charset=utf-8", body)
+    }
+
+    /// Response with a JSON body
+    pub fn json(status: u16, body: &Json) -> Response {
+        Response::new(status, "application/json", body.to_string())
+    }
+
+    /// Plain text response, mostly used for errors
+    pub fn text(status: u16, body: impl Into<Vec<u8>>) -> Response {
+        Response::new(status, "text/plain; charset=utf-8", body)
+    }
+
+    /// 303 See Other redirect, used after a form is posted
+    pub fn redirect(location: &str) -> Response {
+        let mut response = Response::text(303, "");
+        response
+            .headers
+            .push(("Location".to_string(), location.to_string()));
+        response
+    }
+
+    /// Writes the response to the stream. The connection is always closed after the response
+    pub fn write_to(&self, stream: &mut impl Write) -> io::Result<()> {
+        let mut head = format!("HTTP/1.1 {} {}\r\n", self.status, reason(self.status));
+        for (name, value) in self.headers.iter() {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!(
+            "Content-Length: {}\r\nConnection: close\r\n\r\n",
+            self.body.len()
+        ));
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(&self.body)?;
+        stream.flush()
+    }
+}
+
+/// Reason phrase for the status codes used by the servers
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        303 => "See Other",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Reads one request from the connection, passes it to the handler and writes back the response
+fn handle_connection<H>(stream: TcpStream, handler: &H) -> io::Result<()>
+where
+    H: Fn(&Request) -> Response,
+{
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let response = match Request::read(&mut reader) {
+        Ok(request) => handler(&request),
+        Err(e) => Response::text(400, e.to_string()),
+    };
+    let mut stream = stream;
+    response.write_to(&mut stream)
+}
+
+/// Accepts connections on the listener forever, handling each on its own thread
+pub fn serve<H>(listener: TcpListener, handler: H) -> io::Result<()>
+where
+    H: Fn(&Request) -> Response + Send + Sync + 'static,
+{
+    let handler = Arc::new(handler);
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+        let handler = Arc::clone(&handler);
+        thread::spawn(move || {
+            // A client hanging up part way through only affects its own connection
+            let _ = handle_connection(stream, handler.as_ref());
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_oversized_and_malformed_requests() {
+        let huge = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_HEAD_SIZE));
+        assert!(Request::read(&mut huge.as_bytes()).is_err());
+        assert!(Request::read(&mut "nonsense\r\n\r\n".as_bytes()).is_err());
+        let body = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_SIZE + 1
+        );
+        assert!(Request::read(&mut body.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_response_format() {
+        let mut out = Vec::new();
+        Response::redirect("/").write_to(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("HTTP/1.1 303 See Other\r\n"));
+        assert!(out.contains("Location: /\r\n"));
+        assert!(out.ends_with("Content-Length: 0\r\nConnection: close\r\n\r\n"));
+    }
+}
@@ -0,0 +1,356 @@
+//! Endless boards.
+//!
+//! An infinite board has no edges. It is split into square chunks that are only generated the
+//! first time something needs them, each from its own seed made out of the board seed and the
+//! chunk's position, so the same seed always gives the same mines however the board is explored.
+//! Only the chunks that have been generated are kept, in a map keyed by chunk position.
+use crate::{
+    mix, write_column_numbers, write_row_label, write_square, MinesweeperSquare, RevealOutcome,
+    SquareState,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Width and height of a chunk, in squares
+pub const CHUNK_SIZE: usize = 16;
+/// Mines in each chunk unless another density is chosen, about the density of an expert board
+pub const DEFAULT_MINES_PER_CHUNK: usize = 50;
+/// Fewest mines a chunk can have. Below this the empty areas can join up into openings that never
+/// end
+pub const MIN_MINES_PER_CHUNK: usize = 40;
+/// Most mines a chunk can have
+pub const MAX_MINES_PER_CHUNK: usize = 128;
+
+/// Cords of a square on an infinite board. They can be negative
+pub type Cord = (i64, i64);
+
+/// The squares of one chunk, row by row
+struct Chunk {
+    squares: Vec<MinesweeperSquare>,
+}
+
+/// A board without edges, generated chunk by chunk from a seed as it is explored. The squares
+/// around (0, 0) never hold a mine so the first reveal there is always safe
+pub struct InfiniteBoard {
+    seed: u64,
+    mines_per_chunk: usize,
+    chunks: HashMap<Cord, Chunk>,
+    cleared: usize,
+    lost: bool,
+}
+
+/// Splits cords into the chunk they are in and the index of the square within that chunk
+fn locate((x, y): Cord) -> (Cord, usize) {
+    let size = CHUNK_SIZE as i64;
+    let chunk = (x.div_euclid(size), y.div_euclid(size));
+    let index = y.rem_euclid(size) as usize * CHUNK_SIZE + x.rem_euclid(size) as usize;
+    (chunk, index)
+}
+
+/// The eight squares around the cords
+fn neighbours((x, y): Cord) -> impl Iterator<Item = Cord> {
+    (-1..=1)
+        .flat_map(move |dy| (-1..=1).map(move |dx| (dx, dy)))
+        .filter(|&offset| offset != (0, 0))
+        .map(move |(dx, dy)| (x + dx, y + dy))
+}
+
+impl InfiniteBoard {
+    /// Starts an endless board. Every chunk of CHUNK_SIZE by CHUNK_SIZE squares gets
+    /// mines_per_chunk mines, which must be between MIN_MINES_PER_CHUNK and MAX_MINES_PER_CHUNK
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::infinite::{InfiniteBoard, DEFAULT_MINES_PER_CHUNK};
+    /// let board = InfiniteBoard::new(7, DEFAULT_MINES_PER_CHUNK).unwrap();
+    ///
+    /// assert_eq!(board.chunk_count(), 0);
+    /// assert!(InfiniteBoard::new(7, 1).is_err());
+    /// ```
+    pub fn new(seed: u64, mines_per_chunk: usize) -> Result<InfiniteBoard, &'static str> {
+        if mines_per_chunk < MIN_MINES_PER_CHUNK {
+            return Err("Too few mines per chunk, openings would never end");
+        }
+        if mines_per_chunk > MAX_MINES_PER_CHUNK {
+            return Err("Too many mines per chunk");
+        }
+        Ok(InfiniteBoard {
+            seed,
+            mines_per_chunk,
+            chunks: HashMap::new(),
+            cleared: 0,
+            lost: false,
+        })
+    }
+
+    /// Returns the seed the board is generated from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the number of chunks generated so far
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns the number of safe squares revealed, which is the score of an endless game
+    pub fn area_cleared(&self) -> usize {
+        self.cleared
+    }
+
+    /// Returns true once a mine has been revealed
+    pub fn is_lost(&self) -> bool {
+        self.lost
+    }
+
+    /// Generates the chunk at the chunk position from its own seed
+    fn generate(&self, (chunk_x, chunk_y): Cord) -> Chunk {
+        let seed = mix(mix(self.seed ^ mix(chunk_x as u64)) ^ chunk_y as u64);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut squares = vec![MinesweeperSquare::new(); CHUNK_SIZE * CHUNK_SIZE];
+        let mut placed = 0;
+        while placed < self.mines_per_chunk {
+            let index = rng.gen_range(0..squares.len());
+            if squares[index].mines == 0 {
+                squares[index].mines = 1;
+                placed += 1;
+            }
+        }
+        // the chunks around the start lose the mines in the start area, so hold a few less
+        let size = CHUNK_SIZE as i64;
+        for (x, y) in neighbours((0, 0)).chain([(0, 0)]) {
+            if (x.div_euclid(size), y.div_euclid(size)) == (chunk_x, chunk_y) {
+                squares[locate((x, y)).1].mines = 0;
+            }
+        }
+        Chunk { squares }
+    }
+
+    /// Returns the square at the cords, generating its chunk if needed
+    fn square_mut(&mut self, cord: Cord) -> &mut MinesweeperSquare {
+        let (chunk, index) = locate(cord);
+        if !self.chunks.contains_key(&chunk) {
+            let generated = self.generate(chunk);
+            self.chunks.insert(chunk, generated);
+        }
+        &mut self
+            .chunks
+            .get_mut(&chunk)
+            .expect("chunk was just generated")
+            .squares[index]
+    }
+
+    /// Returns the square at the cords, or None if its chunk has not been generated yet and so
+    /// is still entirely hidden
+    pub fn get(&self, cord: Cord) -> Option<&MinesweeperSquare> {
+        let (chunk, index) = locate(cord);
+        self.chunks.get(&chunk).map(|chunk| &chunk.squares[index])
+    }
+
+    /// Returns true if the square at the cords holds a mine, generating its chunk if needed
+    pub fn is_mine_at(&mut self, cord: Cord) -> bool {
+        self.square_mut(cord).is_mine()
+    }
+
+    /// Counts the mines around the square
+    fn check_square(&mut self, cord: Cord) -> i16 {
+        neighbours(cord)
+            .map(|neighbour| i16::from(self.square_mut(neighbour).mines))
+            .sum()
+    }
+
+    /// Reveals the square at the cords, opening up the squares around any square with no mines
+    /// next to it. Returns RevealOutcome::Mine if the square was a mine, otherwise the number of
+    /// squares opened
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::infinite::{InfiniteBoard, DEFAULT_MINES_PER_CHUNK};
+    /// use rusty_mine_sweeper::RevealOutcome;
+    /// let mut board = InfiniteBoard::new(7, DEFAULT_MINES_PER_CHUNK).unwrap();
+    ///
+    /// // the start is always safe
+    /// assert!(matches!(board.reveal((0, 0)), Ok(RevealOutcome::Revealed(_))));
+    /// assert!(board.area_cleared() > 0);
+    /// ```
+    pub fn reveal(&mut self, cord: Cord) -> Result<RevealOutcome, &'static str> {
+        if self.lost {
+            return Err("The game is over");
+        }
+        match self.square_mut(cord).state {
+            SquareState::Revealed => return Err("Square has already been revealed"),
+            SquareState::Flagged => return Err("Square is flagged. Remove the flag first"),
+            SquareState::Hidden | SquareState::Void => {}
+        }
+        if self.square_mut(cord).is_mine() {
+            self.square_mut(cord).state = SquareState::Revealed;
+            self.lost = true;
+            return Ok(RevealOutcome::Mine);
+        }
+
+        let mut revealed = 0;
+        let mut stack = vec![cord];
+        while let Some(cord) = stack.pop() {
+            let square = self.square_mut(cord);
+            if square.state != SquareState::Hidden || square.is_mine() {
+                continue;
+            }
+            let value = self.check_square(cord);
+            let square = self.square_mut(cord);
+            square.value = value;
+            square.state = SquareState::Revealed;
+            revealed += 1;
+            if value == 0 {
+                stack.extend(neighbours(cord));
+            }
+        }
+        self.cleared += revealed;
+        Ok(RevealOutcome::Revealed(revealed))
+    }
+
+    /// Places or removes a flag on the square at the cords.
+    /// Returns true if the square is now flagged and false if the flag was removed
+    pub fn toggle_flag(&mut self, cord: Cord) -> Result<bool, &'static str> {
+        if self.lost {
+            return Err("The game is over");
+        }
+        let square = self.square_mut(cord);
+        match square.state {
+            SquareState::Hidden => {
+                square.state = SquareState::Flagged;
+                square.flags = 1;
+                Ok(true)
+            }
+            SquareState::Flagged => {
+                square.state = SquareState::Hidden;
+                square.flags = 0;
+                Ok(false)
+            }
+            SquareState::Revealed | SquareState::Void => Err("Square has already been revealed"),
+        }
+    }
+
+    /// Returns a view of the width by height squares whose top left square is at the cords
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::infinite::{InfiniteBoard, DEFAULT_MINES_PER_CHUNK};
+    /// let board = InfiniteBoard::new(7, DEFAULT_MINES_PER_CHUNK).unwrap();
+    /// let text = board.view((-2, -1), 3, 2).to_string();
+    ///
+    /// assert_eq!(text.lines().count(), 4);
+    /// assert!(text.starts_with("x -2 to 0, y -1 to 0"));
+    /// ```
+    pub fn view(&self, top_left: Cord, width: usize, height: usize) -> Viewport<'_> {
+        Viewport {
+            board: self,
+            top_left,
+            width,
+            height,
+        }
+    }
+}
+
+/// A rectangle of an infinite board to draw. Rows are labelled with letters and columns with
+/// numbers like a normal board, counted from the top left square of the view
+pub struct Viewport<'a> {
+    board: &'a InfiniteBoard,
+    pub top_left: Cord,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport<'_> {
+    /// Turns a square picked within the view into cords on the board
+    pub fn cord(&self, x: usize, y: usize) -> Cord {
+        (self.top_left.0 + x as i64, self.top_left.1 + y as i64)
+    }
+}
+
+impl fmt::Display for Viewport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (left, top) = self.top_left;
+        writeln!(
+            f,
+            "x {} to {}, y {} to {}",
+            left,
+            left + self.width as i64 - 1,
+            top,
+            top + self.height as i64 - 1
+        )?;
+        write_column_numbers(f, self.width, self.height)?;
+
+        let hidden = MinesweeperSquare::new();
+        for y in 0..self.height {
+            write_row_label(f, y, self.height)?;
+            for x in 0..self.width {
+                if x > 0 {
+                    write!(f, " | ")?;
+                }
+                let square = self.board.get(self.cord(x, y)).unwrap_or(&hidden);
+                write_square(f, square)?;
+            }
+            writeln!(f, " |")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Board;
+
+    #[test]
+    fn test_chunks_do_not_depend_on_exploration_order() {
+        let far = (1000, -4000);
+        let mut first = InfiniteBoard::new(42, DEFAULT_MINES_PER_CHUNK).unwrap();
+        let mut second = InfiniteBoard::new(42, DEFAULT_MINES_PER_CHUNK).unwrap();
+        first.reveal((0, 0)).unwrap();
+        let mines = |board: &mut InfiniteBoard| -> Vec<bool> {
+            (0..CHUNK_SIZE as i64 * 2)
+                .map(|offset| board.is_mine_at((far.0 + offset, far.1 - offset)))
+                .collect()
+        };
+        assert_eq!(mines(&mut second), mines(&mut first));
+        assert!(second.chunk_count() < first.chunk_count());
+
+        let mut other = InfiniteBoard::new(43, DEFAULT_MINES_PER_CHUNK).unwrap();
+        assert_ne!(mines(&mut other), mines(&mut first));
+    }
+
+    #[test]
+    fn test_chunks_hold_the_chosen_mines() {
+        let board = InfiniteBoard::new(3, MIN_MINES_PER_CHUNK).unwrap();
+        let chunk = board.generate((-5, 2));
+        let mines = chunk
+            .squares
+            .iter()
+            .filter(|square| square.is_mine())
+            .count();
+        assert_eq!(mines, MIN_MINES_PER_CHUNK);
+    }
+
+    #[test]
+    fn test_game_ends_on_a_mine() {
+        let mut board = InfiniteBoard::new(9, MAX_MINES_PER_CHUNK).unwrap();
+        board.reveal((0, 0)).unwrap();
+        let mine = (2..).map(|x| (x, 5)).find(|&cord| board.is_mine_at(cord));
+        let cleared = board.area_cleared();
+        assert_eq!(board.reveal(mine.unwrap()), Ok(RevealOutcome::Mine));
+        assert!(board.is_lost());
+        assert_eq!(board.area_cleared(), cleared);
+        assert!(board.toggle_flag((0, 3)).is_err());
+    }
+
+    #[test]
+    fn test_view_columns_line_up_with_board() {
+        let board = InfiniteBoard::new(1, DEFAULT_MINES_PER_CHUNK).unwrap();
+        let text = board.view((0, 0), 12, 3).to_string();
+        let grid: Board<MinesweeperSquare> = Board::isize_board(12, 3);
+        let grid_text = grid.to_string();
+        // the first line of the view gives its cords, then it is drawn like a board
+        assert_eq!(text.lines().nth(1), grid_text.lines().next());
+        assert_eq!(text.lines().nth(2), grid_text.lines().nth(1));
+        assert!(grid_text.lines().next().unwrap().ends_with(" 10  11  12  "));
+    }
+}
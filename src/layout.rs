@@ -0,0 +1,197 @@
+//! Plain text board layouts.
+//!
+//! A layout is one line per row of the board, with one character per square:
+//!
+//! * `.` a hidden safe square and `*` a hidden mine
+//! * `f` a flag on a safe square and `F` a flag on a mine
+//! * `0` to `8` a revealed square, which must match the mines around it
+//! * `X` a revealed mine
+//! * `-` a square that is not part of a shaped board
+//!
+//! Every row must be the same length. Trailing whitespace and blank lines at the end are ignored.
+//! Layouts are for boards with the usual 8 neighbours and at most one mine per square.
+use crate::{Board, MinesweeperSquare, SquareState};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Reads a board from a layout. Errors give the line and column of the problem
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::layout;
+/// let board = layout::parse("01*\n022\n.fF").unwrap();
+///
+/// assert_eq!((board.width, board.height), (3, 3));
+/// assert_eq!(board.mine_count(), 2);
+/// assert_eq!(board.get(1, 0).unwrap().get_value(), 1);
+///
+/// assert_eq!(
+///     layout::parse("..\n.?").err().unwrap(),
+///     "unexpected '?' at line 2, column 2"
+/// );
+/// ```
+pub fn parse(text: &str) -> Result<Board<MinesweeperSquare>, String> {
+    let lines: Vec<&str> = text.lines().map(|line| line.trim_end()).collect();
+    let height = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(0, |last| last + 1);
+    if height == 0 {
+        return Err(String::from("the layout has no rows"));
+    }
+    let width = lines[0].chars().count();
+
+    let mut board: Board<MinesweeperSquare> = Board::isize_board(width, height);
+    let mut numbers = Vec::new();
+    for (y, line) in lines.iter().take(height).enumerate() {
+        let length = line.chars().count();
+        if length != width {
+            return Err(format!(
+                "expected {} squares but found {} at line {}, column {}",
+                width,
+                length,
+                y + 1,
+                length.min(width) + 1
+            ));
+        }
+        for (x, symbol) in line.chars().enumerate() {
+            let square = &mut board[(x, y)];
+            match symbol {
+                '.' => {}
+                '*' => square.mines = 1,
+                'f' | 'F' => {
+                    square.mines = u8::from(symbol == 'F');
+                    square.state = SquareState::Flagged;
+                    square.flags = 1;
+                }
+                'X' => {
+                    square.mines = 1;
+                    square.state = SquareState::Revealed;
+                }
+                '-' => square.state = SquareState::Void,
+                '0'..='8' => {
+                    square.state = SquareState::Revealed;
+                    numbers.push((x, y, symbol as i16 - '0' as i16));
+                }
+                _ => {
+                    return Err(format!(
+                        "unexpected '{}' at line {}, column {}",
+                        symbol,
+                        y + 1,
+                        x + 1
+                    ))
+                }
+            }
+        }
+    }
+
+    // numbers can only be checked once every mine is known
+    board.recount();
+    for (x, y, number) in numbers {
+        let mines = board.check_square(x, y);
+        if mines != number {
+            return Err(format!(
+                "{} does not match the {} mines around it at line {}, column {}",
+                number,
+                mines,
+                y + 1,
+                x + 1
+            ));
+        }
+        board[(x, y)].value = mines;
+    }
+    Ok(board)
+}
+
+/// Writes the board as a layout that parse reads back to the same board
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::layout;
+/// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+/// let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 2);
+/// let mut mine = MinesweeperSquare::new();
+/// mine.set_is_mine(true);
+/// board.set(2, 1, mine);
+/// board.reveal(0, 0).unwrap();
+/// board.toggle_flag(2, 1).unwrap();
+///
+/// assert_eq!(layout::write(&board), "01.\n01F\n");
+/// ```
+pub fn write(board: &Board<MinesweeperSquare>) -> String {
+    let mut text = String::with_capacity((board.width + 1) * board.height);
+    for row in board.iter() {
+        for square in row {
+            let symbol = match square.state {
+                SquareState::Hidden if square.is_mine() => '*',
+                SquareState::Hidden => '.',
+                SquareState::Flagged if square.is_mine() => 'F',
+                SquareState::Flagged => 'f',
+                SquareState::Revealed if square.is_mine() => 'X',
+                // only the larger neighbourhoods can reach 10, which layouts do not support
+                SquareState::Revealed => {
+                    char::from_digit(square.value.max(0) as u32, 10).unwrap_or('?')
+                }
+                SquareState::Void => '-',
+            };
+            text.push(symbol);
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Reads a layout file, see parse for the format
+pub fn load(path: &Path) -> io::Result<Board<MinesweeperSquare>> {
+    let text = fs::read_to_string(path)?;
+    parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Writes the board to a layout file
+pub fn save(path: &Path, board: &Board<MinesweeperSquare>) -> io::Result<()> {
+    fs::write(path, write(board))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RevealOutcome;
+
+    #[test]
+    fn test_round_trip() {
+        let mut board: Board<MinesweeperSquare> = Board::isize_board(8, 6);
+        board.place_mines_seeded(9, 4);
+        let (x, y) = (0..48)
+            .map(|index| (index % 8, index / 8))
+            .find(|&(x, y)| !board[(x, y)].is_mine())
+            .unwrap();
+        board.reveal(x, y).unwrap();
+        let text = write(&board);
+        let hidden = text.find(['.', '*']).unwrap();
+        board.toggle_flag(hidden % 9, hidden / 9).unwrap();
+
+        let text = write(&board);
+        let mut parsed = parse(&text).unwrap();
+        assert_eq!(write(&parsed), text);
+        assert_eq!(parsed.mine_count(), 9);
+        assert_eq!(parsed.is_won(), board.is_won());
+        let hidden = text.find('.').unwrap();
+        let (x, y) = (hidden % 9, hidden / 9);
+        assert!(matches!(
+            parsed.reveal(x, y),
+            Ok(RevealOutcome::Revealed(_))
+        ));
+    }
+
+    #[test]
+    fn test_errors_point_at_the_problem() {
+        assert_eq!(
+            parse("...\n..\n").err().unwrap(),
+            "expected 3 squares but found 2 at line 2, column 3"
+        );
+        assert_eq!(
+            parse("*2.\n...").err().unwrap(),
+            "2 does not match the 1 mines around it at line 1, column 2"
+        );
+        assert!(parse("\n\n").is_err());
+    }
+}
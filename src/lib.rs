@@ -1,9 +1,42 @@
+use builder::FirstClick;
+use cell::Cell;
+use mask::Mask;
 use prompted::*;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::fmt;
-use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
 
-#[derive(Clone, PartialEq, Default, Copy)]
+pub mod api;
+pub mod bitboard;
+pub mod bot;
+pub mod builder;
+pub mod cell;
+pub mod daily;
+pub mod date;
+pub mod engine;
+pub mod flags;
+pub mod hex;
+pub mod http;
+pub mod infinite;
+pub mod json;
+pub mod layout;
+pub mod mask;
+pub mod mbf;
+pub mod puzzle;
+pub mod race;
+pub mod rawvf;
+pub mod report;
+pub mod scores;
+pub mod session;
+pub mod solver;
+pub mod stats;
+pub mod storage;
+pub mod tcp;
+pub mod view;
+pub mod web;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Copy)]
 /// State for the individual squares.
 /// Determines if the square is revealed or if it has been flagged/revealed
 enum SquareState {
@@ -11,15 +44,23 @@ enum SquareState {
     Hidden,
     Revealed,
     Flagged,
+    /// Not part of the board on a shaped board. Never playable and never a neighbour
+    Void,
 }
 
-#[derive(Clone, Copy, Default, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 /// Struct for defining the square on a minesweeper board.
-/// has a state reflecting its state, value reflecting custom value of the square (future use or customization for display) and a boolean to represent if the square contains a mine or is a mine
+/// has a state reflecting its state, value reflecting custom value of the square (future use or customization for display), the number of mines in the square (usually 0 or 1),
+/// the number of flags the player has placed on it and the number of mines in the squares around it
 pub struct MinesweeperSquare {
     state: SquareState,
-    value: isize,
-    is_mine: bool,
+    value: i16,
+    mines: u8,
+    flags: u8,
+    owner: Option<u8>,
+    /// Mines in the neighbouring squares. Worked out as mines are placed and kept up to date by
+    /// the board whenever they move, so it is only meaningful for a square on a board
+    count: u8,
 }
 
 impl MinesweeperSquare {
@@ -33,7 +74,10 @@ impl MinesweeperSquare {
         MinesweeperSquare {
             state: SquareState::Hidden,
             value: -1,
-            is_mine: false,
+            mines: 0,
+            flags: 0,
+            owner: None,
+            count: 0,
         }
     }
 
@@ -46,7 +90,7 @@ impl MinesweeperSquare {
     /// assert_eq!(square.get_value(), -1);
     /// ```
     pub fn get_value(&self) -> isize {
-        self.value
+        self.value as isize
     }
 
     /// Returns a true or false for wether the square contains a mine
@@ -59,47 +103,320 @@ impl MinesweeperSquare {
     /// assert_eq!(square.get_is_mine(), false);
     /// ```
     pub fn get_is_mine(&self) -> bool {
-        self.is_mine
+        self.is_mine()
+    }
+
+    /// Sets wether the square contains a mine
+    ///
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::MinesweeperSquare;
+    /// let mut square: MinesweeperSquare = MinesweeperSquare::new();
+    /// square.set_is_mine(true);
+    ///
+    /// assert_eq!(square.get_is_mine(), true);
+    /// ```
+    pub fn set_is_mine(&mut self, is_mine: bool) {
+        self.mines = u8::from(is_mine);
+    }
+
+    /// Returns the number of mines in the square
+    ///
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::MinesweeperSquare;
+    /// let mut square: MinesweeperSquare = MinesweeperSquare::new();
+    /// square.set_mines(3);
+    ///
+    /// assert_eq!(square.get_mines(), 3);
+    /// assert!(square.get_is_mine());
+    /// ```
+    pub fn get_mines(&self) -> u8 {
+        self.mines
+    }
+
+    /// Sets the number of mines in the square
+    pub fn set_mines(&mut self, mines: u8) {
+        self.mines = mines;
+    }
+
+    /// Returns the number of flags the player has placed on the square
+    pub fn get_flags(&self) -> u8 {
+        self.flags
+    }
+
+    fn is_void(&self) -> bool {
+        self.state == SquareState::Void
+    }
+
+    fn is_mine(&self) -> bool {
+        self.mines > 0
+    }
+
+    /// Returns the player that claimed the square in Minesweeper Flags, if any
+    ///
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::MinesweeperSquare;
+    /// let square: MinesweeperSquare = MinesweeperSquare::new();
+    ///
+    /// assert_eq!(square.get_owner(), None);
+    /// ```
+    pub fn get_owner(&self) -> Option<usize> {
+        self.owner.map(usize::from)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Outcome of revealing or chording on a square.
+/// Either a mine was hit or some number of squares were opened up
+pub enum RevealOutcome {
+    Revealed(usize),
+    Mine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Standard board sizes. Beginner is 9x9 with 10 mines, Intermediate is 16x16 with 40 mines and
+/// Expert is 30x16 with 99 mines
+pub enum Preset {
+    Beginner,
+    Intermediate,
+    Expert,
+}
+
+impl Preset {
+    /// All of the presets from easiest to hardest
+    pub const ALL: [Preset; 3] = [Preset::Beginner, Preset::Intermediate, Preset::Expert];
+
+    /// Returns the (width, height, mines) for the preset
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::Preset;
+    ///
+    /// assert_eq!(Preset::Expert.dimensions(), (30, 16, 99));
+    /// ```
+    pub fn dimensions(&self) -> (usize, usize, usize) {
+        match self {
+            Preset::Beginner => (9, 9, 10),
+            Preset::Intermediate => (16, 16, 40),
+            Preset::Expert => (30, 16, 99),
+        }
+    }
+
+    /// Lower case name of the preset
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::Preset;
+    ///
+    /// assert_eq!(Preset::Intermediate.name(), "intermediate");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::Beginner => "beginner",
+            Preset::Intermediate => "intermediate",
+            Preset::Expert => "expert",
+        }
+    }
+
+    /// Finds the preset with the given name, ignoring case
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::Preset;
+    ///
+    /// assert_eq!(Preset::from_name("Beginner"), Some(Preset::Beginner));
+    /// assert_eq!(Preset::from_name("nightmare"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Preset> {
+        Preset::ALL
+            .into_iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Finds the preset matching the given board size and mine count, if any
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::Preset;
+    ///
+    /// assert_eq!(Preset::matching(9, 9, 10), Some(Preset::Beginner));
+    /// assert_eq!(Preset::matching(9, 9, 11), None);
+    /// ```
+    pub fn matching(width: usize, height: usize, mines: usize) -> Option<Preset> {
+        Preset::ALL
+            .into_iter()
+            .find(|preset| preset.dimensions() == (width, height, mines))
+    }
+
+    /// Creates a new board for the preset with its mines placed
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::Preset;
+    /// let board = Preset::Beginner.board();
+    ///
+    /// assert_eq!((board.width, board.height), (9, 9));
+    /// assert_eq!(board.mine_count(), 10);
+    /// ```
+    pub fn board(&self) -> Board<MinesweeperSquare> {
+        let (width, height, mines) = self.dimensions();
+        let mut board = Board::isize_board(width, height);
+        board.place_mines(mines);
+        board
+    }
+
+    /// Creates a new board for the preset with its mines placed from the seed
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::Preset;
+    ///
+    /// assert_eq!(Preset::Expert.board_with_seed(7).mine_count(), 99);
+    /// ```
+    pub fn board_with_seed(&self, seed: u64) -> Board<MinesweeperSquare> {
+        let (width, height, mines) = self.dimensions();
+        let mut board = Board::isize_board(width, height);
+        board.place_mines_seeded(mines, seed);
+        board
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 /// Board object for any arbitrary type
-/// Values for the board itself and width + height properties for it's limits
+/// Values for the board itself and width + height properties for it's limits. The squares are
+/// kept in one vec, row after row, so the square at (x, y) is at index y * width + x
 pub struct Board<MinesweeperSquare> {
-    board: Vec<Vec<MinesweeperSquare>>,
+    board: Vec<MinesweeperSquare>,
     pub width: usize,
     pub height: usize,
-    _marker: PhantomData<MinesweeperSquare>,
+    topology: Topology,
+    neighbourhood: Neighbourhood,
+    max_mines_per_cell: u8,
+    /// Policy applied on the first reveal and the seed used to move mines for it, taken once used
+    first_click: Option<(FirstClick, u64)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// How the edges of a board behave. On a bounded board squares on the edge have fewer
+/// neighbours, on a toroidal board the edges wrap around so the left edge touches the right
+/// edge and the top touches the bottom
+pub enum Topology {
+    #[default]
+    Bounded,
+    Toroidal,
 }
 
-/// Clone implementation for board of arbitrary type.
-/// Essentially it just clones the vec and cop
-impl Clone for Board<MinesweeperSquare> {
-    fn clone(&self) -> Self {
-        let mut board = Vec::with_capacity(self.height);
-        for row in self.board.iter() {
-            board.push(row.clone());
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// Which squares count as neighbours of a square, for numbers, openings, chords and the solver.
+/// King is the usual 8 surrounding squares, Orthogonal only the 4 squares sharing an edge,
+/// Knight the 8 squares a knight's move away and Radius2 the 24 squares within two steps.
+/// Hex lays the board out as hexagons with every odd row shifted half a square to the right,
+/// giving each cell 6 neighbours
+pub enum Neighbourhood {
+    #[default]
+    King,
+    Orthogonal,
+    Knight,
+    Radius2,
+    Hex,
+}
+
+/// Squares within two steps in any direction
+const RADIUS_2_OFFSETS: [(isize, isize); 24] = {
+    let mut offsets = [(0, 0); 24];
+    let mut i = 0;
+    let mut y = -2;
+    while y <= 2 {
+        let mut x = -2;
+        while x <= 2 {
+            if x != 0 || y != 0 {
+                offsets[i] = (x, y);
+                i += 1;
+            }
+            x += 1;
         }
-        Board {
-            board,
-            width: self.width,
-            height: self.height,
-            _marker: PhantomData,
+        y += 1;
+    }
+    offsets
+};
+
+impl Neighbourhood {
+    pub const ALL: [Neighbourhood; 5] = [
+        Neighbourhood::King,
+        Neighbourhood::Orthogonal,
+        Neighbourhood::Knight,
+        Neighbourhood::Radius2,
+        Neighbourhood::Hex,
+    ];
+
+    /// Returns the lowercase name of the neighbourhood
+    pub fn name(&self) -> &'static str {
+        match self {
+            Neighbourhood::King => "king",
+            Neighbourhood::Orthogonal => "orthogonal",
+            Neighbourhood::Knight => "knight",
+            Neighbourhood::Radius2 => "radius2",
+            Neighbourhood::Hex => "hex",
+        }
+    }
+
+    /// Looks a neighbourhood up by its name, ignoring case
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::Neighbourhood;
+    ///
+    /// assert_eq!(Neighbourhood::from_name("Knight"), Some(Neighbourhood::Knight));
+    /// assert_eq!(Neighbourhood::from_name("queen"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Neighbourhood> {
+        Neighbourhood::ALL
+            .into_iter()
+            .find(|neighbourhood| neighbourhood.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Returns the (x, y) offsets from a square in row y to its neighbours
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::Neighbourhood;
+    ///
+    /// assert_eq!(Neighbourhood::King.offsets(0).len(), 8);
+    /// assert_eq!(Neighbourhood::Orthogonal.offsets(0).len(), 4);
+    /// assert!(Neighbourhood::Knight.offsets(0).contains(&(1, 2)));
+    /// assert_eq!(Neighbourhood::Radius2.offsets(0).len(), 24);
+    /// assert!(Neighbourhood::Hex.offsets(0).contains(&(-1, -1)));
+    /// assert!(Neighbourhood::Hex.offsets(1).contains(&(1, -1)));
+    /// ```
+    pub fn offsets(&self, y: usize) -> &'static [(isize, isize)] {
+        match self {
+            Neighbourhood::King => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+            Neighbourhood::Orthogonal => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Neighbourhood::Knight => &[
+                (-1, -2),
+                (1, -2),
+                (-2, -1),
+                (2, -1),
+                (-2, 1),
+                (2, 1),
+                (-1, 2),
+                (1, 2),
+            ],
+            Neighbourhood::Radius2 => &RADIUS_2_OFFSETS,
+            Neighbourhood::Hex if y.is_multiple_of(2) => hex::EVEN_ROW_OFFSETS,
+            Neighbourhood::Hex => hex::ODD_ROW_OFFSETS,
         }
     }
 }
 
 /// Default type for the board of type MinesweeperSquare.
-/// used to init a basic and small board that can be used to play minesweeper
+/// used to init a basic and small 5x5 board with no mines that can be used to play minesweeper
 impl Default for Board<MinesweeperSquare> {
     fn default() -> Self {
-        Board {
-            board: Vec::new(),
-            width: 5,
-            height: 5,
-            _marker: PhantomData,
-        }
+        Board::isize_board(5, 5)
     }
 }
 
@@ -135,19 +452,14 @@ where
     /// assert_eq!(board.height, height);
     /// ```
     pub fn new(width: usize, height: usize) -> Board<MinesweeperSquare> {
-        let mut board = Vec::with_capacity(height);
-        for _ in 0..height {
-            let mut row = Vec::with_capacity(width);
-            for _ in 0..width {
-                row.push(MinesweeperSquare::default());
-            }
-            board.push(row);
-        }
         Board {
-            board,
+            board: vec![MinesweeperSquare::default(); width * height],
             width,
             height,
-            _marker: PhantomData,
+            topology: Topology::Bounded,
+            neighbourhood: Neighbourhood::King,
+            max_mines_per_cell: 1,
+            first_click: None,
         }
     }
 
@@ -159,40 +471,61 @@ where
     /// let width = 4;
     /// let height = 3;
     /// let board: Board<i32> = Board::new(width, height);
-
+    ///
     /// assert!(board.get(width, height).is_none());
     /// assert!(board.get(width + 1, 0).is_none());
     /// assert!(board.get(0, height + 1).is_none());
     /// ```
     pub fn get(&self, x: usize, y: usize) -> Option<&MinesweeperSquare> {
-        self.board.get(y).and_then(|row| row.get(x))
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.board.get(y * self.width + x)
     }
 
-    /// Sets the value for a given index to the value passed in on the board
+    /// Gets a mutable reference to the element at the x and y coordinates, None if out of bounds
     /// # Examples
     /// ```
     /// use rusty_mine_sweeper::Board;
-    /// let width = 4;
-    /// let height = 3;
-    /// let mut value = 1;
-    /// let mut board: Board<usize> = Board::new(width, height);
-    ///
-    /// board.set(0, 0, value);
-    /// assert!(board.get(0, 0) == Some(&value));
-    /// value = 3;
-    /// board.set(2, 2, value);
-    /// assert!(board.get(2, 2) == Some(&value));
-    /// value = 5;
-    /// board.set(1, 1, value);
-    /// assert!(board.get(1, 1) == Some(&value));
+    /// let mut board: Board<i32> = Board::new(4, 3);
+    /// *board.get_mut(1, 2).unwrap() = 7;
+    ///
+    /// assert_eq!(board[(1, 2)], 7);
+    /// assert!(board.get_mut(4, 0).is_none());
     /// ```
-    pub fn set(&mut self, x: usize, y: usize, value: MinesweeperSquare) {
-        if y < self.height && x < self.width {
-            self.board[y][x] = value;
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut MinesweeperSquare> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.board.get_mut(y * self.width + x)
+    }
+
+    /// Returns the row at y as a slice, None if out of bounds
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::Board;
+    /// let mut board: Board<i32> = Board::new(4, 3);
+    /// board[(2, 1)] = 5;
+    ///
+    /// assert_eq!(board.row(1), Some(&[0, 0, 5, 0][..]));
+    /// assert!(board.row(3).is_none());
+    /// ```
+    pub fn row(&self, y: usize) -> Option<&[MinesweeperSquare]> {
+        if y >= self.height {
+            return None;
+        }
+        Some(&self.board[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Returns the row at y as a mutable slice, None if out of bounds
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [MinesweeperSquare]> {
+        if y >= self.height {
+            return None;
         }
+        Some(&mut self.board[y * self.width..(y + 1) * self.width])
     }
 
-    /// Creates a Iterator that gives a mutable reference to the values on the board
+    /// Creates a Iterator that gives a mutable slice of each row of the board
     /// # Examples
     /// ```
     /// use rusty_mine_sweeper::Board;
@@ -210,11 +543,12 @@ where
     ///     }
     /// }
     /// ```
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Vec<MinesweeperSquare>> {
-        self.board.iter_mut()
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut [MinesweeperSquare]> {
+        // chunks of 0 panic, and a board without columns has no squares to hand out anyway
+        self.board.chunks_mut(self.width.max(1))
     }
 
-    /// Creates a Iterator that gives a reference to the values on the board
+    /// Creates a Iterator that gives a slice of each row of the board
     /// # Examples
     /// ```
     /// use rusty_mine_sweeper::Board;
@@ -228,8 +562,48 @@ where
     ///     }
     /// }
     /// ```
-    pub fn iter(&self) -> impl Iterator<Item = &Vec<MinesweeperSquare>> {
-        self.board.iter()
+    pub fn iter(&self) -> impl Iterator<Item = &[MinesweeperSquare]> {
+        self.board.chunks(self.width.max(1))
+    }
+}
+
+/// Indexes the board by (x, y) cords
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::Board;
+/// let mut board: Board<i32> = Board::new(4, 3);
+/// board[(3, 2)] = 9;
+///
+/// assert_eq!(board[(3, 2)], 9);
+/// assert_eq!(board.get(3, 2), Some(&9));
+/// ```
+impl<MinesweeperSquare> Index<(usize, usize)> for Board<MinesweeperSquare> {
+    type Output = MinesweeperSquare;
+
+    fn index(&self, (x, y): (usize, usize)) -> &MinesweeperSquare {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is off the {}x{} board",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &self.board[y * self.width + x]
+    }
+}
+
+impl<MinesweeperSquare> IndexMut<(usize, usize)> for Board<MinesweeperSquare> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut MinesweeperSquare {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is off the {}x{} board",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        &mut self.board[y * self.width + x]
     }
 }
 
@@ -242,23 +616,263 @@ impl Board<MinesweeperSquare> {
     /// let mut board: Board<MinesweeperSquare> = Board::isize_board(5, 4);
     /// ```
     pub fn isize_board(width: usize, height: usize) -> Board<MinesweeperSquare> {
-        let mut board = Vec::with_capacity(height);
-        for _ in 0..height {
-            let row = vec![
-                MinesweeperSquare {
-                    value: -1,
-                    state: SquareState::Hidden,
-                    is_mine: false
-                };
-                width
-            ];
-            board.push(row);
-        }
+        Board::isize_board_with_topology(width, height, Topology::Bounded)
+    }
+
+    /// Creates a board of hidden squares where the edges behave as given by the topology
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare, RevealOutcome, Topology};
+    /// let mut board: Board<MinesweeperSquare> =
+    ///     Board::isize_board_with_topology(5, 1, Topology::Toroidal);
+    /// let mut mine = MinesweeperSquare::new();
+    /// mine.set_is_mine(true);
+    /// board.set(4, 0, mine);
+    ///
+    /// // the first square touches the mine on the far edge
+    /// assert_eq!(board.topology(), Topology::Toroidal);
+    /// assert_eq!(board.reveal(0, 0), Ok(RevealOutcome::Revealed(1)));
+    /// assert_eq!(board.get(0, 0).unwrap().get_value(), 1);
+    /// ```
+    pub fn isize_board_with_topology(
+        width: usize,
+        height: usize,
+        topology: Topology,
+    ) -> Board<MinesweeperSquare> {
+        Board::isize_board_with_neighbourhood(width, height, topology, Neighbourhood::King)
+    }
+
+    /// Creates a board of hidden squares with the given edges and neighbourhood. A wrapped hex
+    /// board with an odd height gets one more row, as the shifted rows only line up across the
+    /// wrap when there is an even number of them
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare, Neighbourhood, Topology};
+    /// let board: Board<MinesweeperSquare> =
+    ///     Board::isize_board_with_neighbourhood(6, 4, Topology::Bounded, Neighbourhood::Hex);
+    /// let torus: Board<MinesweeperSquare> =
+    ///     Board::isize_board_with_neighbourhood(6, 5, Topology::Toroidal, Neighbourhood::Hex);
+    ///
+    /// assert_eq!(board.neighbourhood(), Neighbourhood::Hex);
+    /// assert_eq!(torus.height, 6);
+    /// ```
+    pub fn isize_board_with_neighbourhood(
+        width: usize,
+        height: usize,
+        topology: Topology,
+        neighbourhood: Neighbourhood,
+    ) -> Board<MinesweeperSquare> {
+        let height = if neighbourhood == Neighbourhood::Hex && topology == Topology::Toroidal {
+            height + height % 2
+        } else {
+            height
+        };
         Board {
-            board,
+            board: vec![MinesweeperSquare::new(); width * height],
             width,
             height,
-            _marker: PhantomData,
+            topology,
+            neighbourhood,
+            max_mines_per_cell: 1,
+            first_click: None,
+        }
+    }
+
+    /// Turns every square outside the mask into a void square, which is not part of the board.
+    /// The mask must be the same size as the board and be applied before any mines are placed
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::mask::Mask;
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+    /// board.apply_mask(&Mask::with_hole(9, 9)).unwrap();
+    ///
+    /// assert!(board.is_shaped());
+    /// assert_eq!(board.square_count(), 72);
+    /// assert!(board.reveal(4, 4).is_err());
+    /// ```
+    pub fn apply_mask(&mut self, mask: &Mask) -> Result<(), &'static str> {
+        if mask.width != self.width || mask.height != self.height {
+            return Err("Mask is not the same size as the board");
+        }
+        if self.mine_count() > 0 || self.is_shaped() {
+            return Err("Mask must be applied to a new board");
+        }
+        for (index, square) in self.board.iter_mut().enumerate() {
+            if !mask.contains(index % mask.width, index / mask.width) {
+                square.state = SquareState::Void;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if some squares of the board were cut away by a mask
+    pub fn is_shaped(&self) -> bool {
+        self.board
+            .iter()
+            .any(|square| square.state == SquareState::Void)
+    }
+
+    /// Returns the number of squares that are part of the board
+    pub fn square_count(&self) -> usize {
+        self.board
+            .iter()
+            .filter(|square| square.state != SquareState::Void)
+            .count()
+    }
+
+    /// Returns how the edges of the board behave
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Returns which squares count as neighbours on the board
+    pub fn neighbourhood(&self) -> Neighbourhood {
+        self.neighbourhood
+    }
+
+    /// Sets the square at the given cords, keeping the numbers of the squares around it up to date
+    /// with any mines it adds or removes
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(4, 3);
+    /// let mut mine = MinesweeperSquare::new();
+    /// mine.set_is_mine(true);
+    ///
+    /// board.set(1, 1, mine);
+    /// assert!(board.get(1, 1).unwrap().get_is_mine());
+    /// assert_eq!(board.mines_around(0, 0), Some(1));
+    /// board.set(1, 1, MinesweeperSquare::new());
+    /// assert_eq!(board.mines_around(0, 0), Some(0));
+    /// ```
+    pub fn set(&mut self, x: usize, y: usize, value: MinesweeperSquare) {
+        let Some(square) = self.get_mut(x, y) else {
+            return;
+        };
+        let mines = value.mines;
+        *square = MinesweeperSquare {
+            mines: square.mines,
+            count: square.count,
+            ..value
+        };
+        self.change_mines(x, y, mines);
+    }
+
+    /// Returns the number of mines around the square, whether or not it has been revealed.
+    /// None if the cords are off the board or not part of it
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+    /// board.place_mines_seeded(10, 1);
+    ///
+    /// let total: usize = (0..9)
+    ///     .flat_map(|y| (0..9).map(move |x| (x, y)))
+    ///     .map(|(x, y)| board.mines_around(x, y).unwrap() as usize)
+    ///     .sum();
+    /// // every mine is counted once by each of its neighbours
+    /// assert!(total >= 10 && total <= 80);
+    /// assert_eq!(board.mines_around(9, 0), None);
+    /// ```
+    pub fn mines_around(&self, x: usize, y: usize) -> Option<u8> {
+        self.get(x, y)
+            .filter(|square| !square.is_void())
+            .map(|square| square.count)
+    }
+
+    /// Returns what can be seen of the square at the given cords, None if they are off the board
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::cell::CellState;
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 3);
+    /// board.toggle_flag(1, 1).unwrap();
+    ///
+    /// assert_eq!(board.cell(1, 1).unwrap().state, CellState::Flagged(1));
+    /// assert_eq!(board.cell(0, 0).unwrap().state, CellState::Hidden);
+    /// assert!(board.cell(0, 3).is_none());
+    /// ```
+    pub fn cell(&self, x: usize, y: usize) -> Option<Cell> {
+        (x < self.width && y < self.height).then(|| Cell::new(self, x, y))
+    }
+
+    /// Iterates over every square of the board as a cell, row after row, including void squares
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::cell::CellState;
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(4, 2);
+    /// board.reveal(0, 0).unwrap();
+    ///
+    /// assert_eq!(board.cells().count(), 8);
+    /// assert!(board.cells().all(|cell| cell.state == CellState::Revealed(0)));
+    /// ```
+    pub fn cells(&self) -> impl Iterator<Item = Cell> + '_ {
+        (0..self.board.len()).map(|index| Cell::new(self, index % self.width, index / self.width))
+    }
+
+    /// Iterates over the neighbours of (x, y) as cells. This is the cell version of neighbours,
+    /// which gives their cords instead; both keep the British spelling used across the crate
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let board: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+    ///
+    /// assert_eq!(board.neighbour_cells(0, 0).count(), 3);
+    /// assert!(board.neighbour_cells(4, 4).all(|cell| cell.is_hidden()));
+    /// ```
+    pub fn neighbour_cells(&self, x: usize, y: usize) -> impl Iterator<Item = Cell> + '_ {
+        self.neighbours(x, y).map(|(x, y)| Cell::new(self, x, y))
+    }
+
+    /// Iterates over the squares that have not been revealed or flagged yet
+    pub fn hidden_cells(&self) -> impl Iterator<Item = Cell> + '_ {
+        self.cells().filter(Cell::is_hidden)
+    }
+
+    /// Iterates over the squares with flags on them
+    pub fn flagged_cells(&self) -> impl Iterator<Item = Cell> + '_ {
+        self.cells().filter(Cell::is_flagged)
+    }
+
+    /// Works out the number of mines around every square again. Only needed after changing mines
+    /// through index_mut, get_mut, row_mut or iter_mut, as every other way of placing or moving
+    /// mines keeps the numbers up to date
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 3);
+    /// board[(1, 1)].set_is_mine(true);
+    ///
+    /// assert_eq!(board.mines_around(0, 0), Some(0));
+    /// board.recount();
+    /// assert_eq!(board.mines_around(0, 0), Some(1));
+    /// ```
+    pub fn recount(&mut self) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let count = self.neighbours(x, y).map(|cord| self[cord].mines).sum();
+                self[(x, y)].count = count;
+            }
+        }
+    }
+
+    /// Sets the number of mines on a square and updates the count of every square around it
+    fn change_mines(&mut self, x: usize, y: usize, mines: u8) {
+        let before = self[(x, y)].mines;
+        self[(x, y)].mines = mines;
+        // no neighbourhood has more than 24 squares, which saves allocating on every mine
+        let mut neighbours = [0; RADIUS_2_OFFSETS.len()];
+        let mut found = 0;
+        for (x_index, y_index) in self.neighbours(x, y) {
+            neighbours[found] = y_index * self.width + x_index;
+            found += 1;
+        }
+        for &index in &neighbours[..found] {
+            let square = &mut self.board[index];
+            // saturating, as a mine added through index_mut without a recount was never counted
+            square.count = square.count.saturating_add(mines).saturating_sub(before);
         }
     }
 
@@ -284,187 +898,875 @@ impl Board<MinesweeperSquare> {
     /// assert_eq!(count, 10);
     /// ```
     pub fn increase_difficulty(&mut self) {
-        let total_squares = self.width * self.height;
+        let total_squares = self.square_count();
         let mines_count = total_squares / 10; // Approximately 10% of total squares
 
-        let mut rng = rand::thread_rng();
+        self.place_mines(mines_count);
+    }
 
-        for _ in 0..mines_count {
+    /// Randomly places the given number of mines on squares that do not already contain a mine.
+    /// Stops early if the board runs out of free squares
+    ///
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::Board;
+    /// use rusty_mine_sweeper::MinesweeperSquare;
+    ///
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+    /// board.place_mines(10);
+    ///
+    /// assert_eq!(board.mine_count(), 10);
+    /// ```
+    pub fn place_mines(&mut self, count: usize) {
+        self.place_mines_with_rng(count, &mut rand::thread_rng());
+    }
+
+    /// Places the given number of mines using a random number generator seeded with the seed.
+    /// The same seed always gives the same layout for a board of the same size
+    ///
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::Board;
+    /// use rusty_mine_sweeper::MinesweeperSquare;
+    ///
+    /// let mut first: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+    /// let mut second: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+    /// first.place_mines_seeded(10, 42);
+    /// second.place_mines_seeded(10, 42);
+    ///
+    /// for (first_row, second_row) in first.iter().zip(second.iter()) {
+    ///     assert!(first_row == second_row);
+    /// }
+    /// ```
+    pub fn place_mines_seeded(&mut self, count: usize, seed: u64) {
+        self.place_mines_with_rng(count, &mut StdRng::seed_from_u64(seed));
+    }
+
+    /// Places the given number of mines using the random number generator
+    fn place_mines_with_rng<R: Rng>(&mut self, count: usize, rng: &mut R) {
+        let cap = self.max_mines_per_cell;
+        let free_space = (self.square_count() * cap as usize).saturating_sub(self.mine_count());
+
+        for _ in 0..count.min(free_space) {
             let mut placed = false;
             while !placed {
                 let x = rng.gen_range(0..self.width);
                 let y = rng.gen_range(0..self.height);
 
-                // Place a mine if the cell is not already a mine
-                if !self.board[y][x].is_mine {
-                    self.board[y][x].is_mine = true;
+                // Place a mine if the cell is on the board and has room for another
+                let square = &self[(x, y)];
+                if square.state != SquareState::Void && square.mines < cap {
+                    self.change_mines(x, y, square.mines + 1);
                     placed = true;
                 }
             }
         }
     }
 
-    // Checks any given square for the number of bombs around it aka the number -10 and will assign itself a given number reflecting that
-    fn check_square(&self, x: usize, y: usize) -> isize {
-        let mut count = 0;
-        for y_index in y.saturating_sub(1)..=y + 1 {
-            for x_index in x.saturating_sub(1)..=x + 1 {
-                if x_index >= self.width || y_index >= self.height {
-                    continue;
-                }
-                if self.board[y_index][x_index].is_mine {
-                    count += 1;
+    /// Randomly places the given number of mines, letting each square hold up to max_per_cell
+    /// of them, at most 10. Numbers then show the total number of mines around a square, and a square has
+    /// to be flagged once for each of its mines to win
+    ///
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::Board;
+    /// use rusty_mine_sweeper::MinesweeperSquare;
+    ///
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 3);
+    /// board.place_multi_mines(20, 3);
+    ///
+    /// assert_eq!(board.mine_count(), 20);
+    /// assert_eq!(board.max_mines_per_cell(), 3);
+    /// assert!(board.iter().flatten().all(|square| square.get_mines() <= 3));
+    /// ```
+    pub fn place_multi_mines(&mut self, count: usize, max_per_cell: u8) {
+        self.set_max_mines_per_cell(max_per_cell);
+        self.place_mines(count);
+    }
+
+    /// Places mines like place_multi_mines using a random number generator seeded with the seed
+    pub fn place_multi_mines_seeded(&mut self, count: usize, max_per_cell: u8, seed: u64) {
+        self.set_max_mines_per_cell(max_per_cell);
+        self.place_mines_seeded(count, seed);
+    }
+
+    /// Sets the most mines a square can hold, never below what a square already holds so a mine
+    /// that is already on the board always fits
+    fn set_max_mines_per_cell(&mut self, max_per_cell: u8) {
+        let held = self
+            .board
+            .iter()
+            .map(|square| square.mines)
+            .max()
+            .unwrap_or(0);
+        self.max_mines_per_cell = max_per_cell.max(held).clamp(1, MAX_MINES_PER_CELL);
+    }
+
+    /// Returns the most mines a single square can hold, 1 on an ordinary board
+    pub fn max_mines_per_cell(&self) -> u8 {
+        self.max_mines_per_cell
+    }
+
+    /// Moves every mine on the listed squares to random squares off the list, keeping the numbers
+    /// around both up to date. Used to make sure a first click is safe without placing the mines
+    /// again. The board is left unchanged if there is not enough room for the mines
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare, RevealOutcome};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+    /// board.place_mines(20);
+    /// let clear: Vec<_> = board.neighbours(4, 4).chain([(4, 4)]).collect();
+    ///
+    /// board.relocate_mines(&clear).unwrap();
+    /// assert_eq!(board.mine_count(), 20);
+    /// assert_eq!(board.mines_around(4, 4), Some(0));
+    /// assert!(matches!(board.reveal(4, 4), Ok(RevealOutcome::Revealed(_))));
+    /// ```
+    pub fn relocate_mines(&mut self, keep_clear: &[(usize, usize)]) -> Result<(), &'static str> {
+        self.relocate_mines_with_rng(keep_clear, &mut rand::thread_rng())
+    }
+
+    /// Moves the mines like relocate_mines using a random number generator seeded with the seed
+    pub fn relocate_mines_seeded(
+        &mut self,
+        keep_clear: &[(usize, usize)],
+        seed: u64,
+    ) -> Result<(), &'static str> {
+        self.relocate_mines_with_rng(keep_clear, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// Moves the mines off the listed squares using the random number generator
+    fn relocate_mines_with_rng<R: Rng>(
+        &mut self,
+        keep_clear: &[(usize, usize)],
+        rng: &mut R,
+    ) -> Result<(), &'static str> {
+        let cap = self.max_mines_per_cell;
+        let mut cleared = vec![false; self.board.len()];
+        for &(x, y) in keep_clear {
+            if x < self.width && y < self.height {
+                cleared[y * self.width + x] = true;
+            }
+        }
+        let mut moving = 0;
+        let mut free_space = 0;
+        for (square, cleared) in self.board.iter().zip(&cleared) {
+            if *cleared {
+                moving += usize::from(square.mines);
+            } else if !square.is_void() {
+                free_space += usize::from(cap.saturating_sub(square.mines));
+            }
+        }
+        if moving > free_space {
+            return Err("Not enough free squares to move the mines to");
+        }
+
+        for (index, _) in cleared.iter().enumerate().filter(|(_, cleared)| **cleared) {
+            self.change_mines(index % self.width, index / self.width, 0);
+        }
+        for _ in 0..moving {
+            loop {
+                let x = rng.gen_range(0..self.width);
+                let y = rng.gen_range(0..self.height);
+                let square = &self[(x, y)];
+                if !cleared[y * self.width + x] && !square.is_void() && square.mines < cap {
+                    self.change_mines(x, y, square.mines + 1);
+                    break;
                 }
             }
         }
-        count
+        Ok(())
+    }
+
+    /// Iterates over the cords of the neighbours of (x, y) in the board's neighbourhood. On a
+    /// bounded board squares past the edges are left out, on a toroidal board they wrap around to
+    /// the other side. Void squares are never neighbours
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare, Neighbourhood, Topology};
+    /// let board: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+    /// let torus: Board<MinesweeperSquare> =
+    ///     Board::isize_board_with_topology(9, 9, Topology::Toroidal);
+    ///
+    /// assert_eq!(board.neighbours(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1), (1, 1)]);
+    /// assert_eq!(board.neighbours(4, 4).count(), 8);
+    /// assert!(torus.neighbours(0, 0).any(|cord| cord == (8, 8)));
+    /// ```
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let offsets = self.neighbourhood.offsets(y);
+        let wraps = self.topology == Topology::Toroidal;
+        offsets.iter().enumerate().filter_map(move |(i, &offset)| {
+            let (x_index, y_index) = self.offset_cord(x, y, offset)?;
+            // on small toroidal boards a square can be its own neighbour or be reached more than
+            // once, so only the first offset reaching it counts
+            if wraps
+                && ((x_index, y_index) == (x, y)
+                    || offsets[..i].iter().any(|&earlier| {
+                        self.offset_cord(x, y, earlier) == Some((x_index, y_index))
+                    }))
+            {
+                return None;
+            }
+            let square = &self.board[y_index * self.width + x_index];
+            (!square.is_void()).then_some((x_index, y_index))
+        })
+    }
+
+    /// Moves (x, y) by the offset, following the board's topology. None if it falls off a
+    /// bounded board
+    fn offset_cord(
+        &self,
+        x: usize,
+        y: usize,
+        (x_offset, y_offset): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        match self.topology {
+            Topology::Bounded => Some((
+                x.checked_add_signed(x_offset).filter(|&x| x < self.width)?,
+                y.checked_add_signed(y_offset)
+                    .filter(|&y| y < self.height)?,
+            )),
+            Topology::Toroidal => Some((
+                (x as isize + x_offset).rem_euclid(self.width as isize) as usize,
+                (y as isize + y_offset).rem_euclid(self.height as isize) as usize,
+            )),
+        }
+    }
+
+    // Checks any given square for the number of bombs around it aka the number -10 and will assign itself a given number reflecting that
+    fn check_square(&self, x: usize, y: usize) -> i16 {
+        i16::from(self[(x, y)].count)
     }
 
     /// updates the board state given a (x, y) cords. This involves updating the square itself as revealed
-    /// then updating its mine proximity count
-    fn update_board(&mut self, x: usize, y: usize) {
-        // First, update the clicked square itself
-        self.board[y][x].value = self.check_square(x, y);
-        self.board[y][x].state = SquareState::Revealed;
-
-        // Then, update each of the eight surrounding squares
-        for y_index in y.saturating_sub(1)..=y + 1 {
-            for x_index in x.saturating_sub(1)..=x + 1 {
-                // Skip the clicked square itself, as it's already updated
-                if x_index == x && y_index == y {
-                    continue;
-                }
-                if x_index < self.width && y_index < self.height && x == x_index && y_index == y {
-                    // Update each surrounding square
-                    self.board[y_index][x_index].value = self.check_square(x_index, y_index);
+    /// then updating its mine proximity count. Squares with no mines around them open up their
+    /// neighbours as well (flood fill). Returns the number of squares revealed
+    fn update_board(&mut self, x: usize, y: usize) -> usize {
+        if !self.open_square(x, y) {
+            return 0;
+        }
+        let mut revealed = 1;
+        // squares are opened as they are pushed so each one is only ever pushed once
+        let mut stack = vec![(x, y)];
+        let mut opened = Vec::new();
+        while let Some((x, y)) = stack.pop() {
+            if self[(x, y)].value != 0 {
+                continue;
+            }
+            opened.extend(self.neighbours(x, y));
+            for (x, y) in opened.drain(..) {
+                if self.open_square(x, y) {
+                    revealed += 1;
+                    stack.push((x, y));
                 }
             }
         }
+        revealed
     }
 
-    /// Gets input from the user and makes the given move
+    /// Reveals a single hidden safe square and works out its number. Returns false if the square
+    /// could not be opened
+    fn open_square(&mut self, x: usize, y: usize) -> bool {
+        if self[(x, y)].state != SquareState::Hidden || self[(x, y)].is_mine() {
+            return false;
+        }
+        self[(x, y)].value = self.check_square(x, y);
+        self[(x, y)].state = SquareState::Revealed;
+        true
+    }
+
+    /// Flags every mine once all of the safe squares have been revealed, so that a cleared board
+    /// also satisfies is_won()
+    fn flag_remaining_mines(&mut self) {
+        if !self.is_cleared() {
+            return;
+        }
+        for square in self.board.iter_mut() {
+            if square.is_mine() {
+                square.state = SquareState::Flagged;
+                square.flags = square.mines;
+            }
+        }
+    }
+
+    /// Reveals the square at the given (x, y) cords without prompting the user.
+    /// Returns RevealOutcome::Mine if the square was a mine, otherwise the number of squares opened
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare, RevealOutcome};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 3);
+    ///
+    /// // no mines on the board so the whole board opens up at once
+    /// assert_eq!(board.reveal(0, 0), Ok(RevealOutcome::Revealed(9)));
+    /// assert!(board.reveal(1, 1).is_err());
+    /// assert!(board.reveal(3, 0).is_err());
+    /// ```
+    pub fn reveal(&mut self, x: usize, y: usize) -> Result<RevealOutcome, &'static str> {
+        if x >= self.width || y >= self.height {
+            return Err("Square selected is out of bounds");
+        }
+        match self[(x, y)].state {
+            SquareState::Revealed => return Err("Square has already been revealed"),
+            SquareState::Flagged => return Err("Square is flagged. Remove the flag first"),
+            SquareState::Void => return Err(VOID_SQUARE_ERROR),
+            SquareState::Hidden => {}
+        }
+        if let Some((first_click, seed)) = self.first_click.take() {
+            let clear = first_click.keep_clear(self, x, y);
+            // fall back to only the square itself when there is no room to clear an opening
+            if self.relocate_mines_seeded(&clear, seed).is_err() {
+                let _ = self.relocate_mines_seeded(&[(x, y)], seed);
+            }
+        }
+        if self[(x, y)].is_mine() {
+            self[(x, y)].state = SquareState::Revealed;
+            return Ok(RevealOutcome::Mine);
+        }
+        let revealed = self.update_board(x, y);
+        self.flag_remaining_mines();
+        Ok(RevealOutcome::Revealed(revealed))
+    }
+
+    /// Places or removes a flag on the square at the given (x, y) cords.
+    /// Returns true if the square is now flagged and false if the flag was removed
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 3);
+    ///
+    /// assert_eq!(board.toggle_flag(1, 1), Ok(true));
+    /// assert_eq!(board.toggle_flag(1, 1), Ok(false));
+    /// ```
+    pub fn toggle_flag(&mut self, x: usize, y: usize) -> Result<bool, &'static str> {
+        if x >= self.width || y >= self.height {
+            return Err("Square selected is out of bounds");
+        }
+        let square = &mut self[(x, y)];
+        match square.state {
+            SquareState::Hidden => {
+                square.state = SquareState::Flagged;
+                square.flags = 1;
+                Ok(true)
+            }
+            SquareState::Flagged => {
+                square.state = SquareState::Hidden;
+                square.flags = 0;
+                Ok(false)
+            }
+            SquareState::Revealed => Err("Square has already been revealed"),
+            SquareState::Void => Err(VOID_SQUARE_ERROR),
+        }
+    }
+
+    /// Adds another flag to the square at the given (x, y) cords, for boards where squares can
+    /// hold more than one mine. Once the square has as many flags as a square can hold mines
+    /// the flags are all removed. Returns the number of flags now on the square
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 3);
+    /// board.place_multi_mines(0, 2);
+    ///
+    /// assert_eq!(board.cycle_flag(1, 1), Ok(1));
+    /// assert_eq!(board.cycle_flag(1, 1), Ok(2));
+    /// assert_eq!(board.cycle_flag(1, 1), Ok(0));
+    /// ```
+    pub fn cycle_flag(&mut self, x: usize, y: usize) -> Result<u8, &'static str> {
+        if x >= self.width || y >= self.height {
+            return Err("Square selected is out of bounds");
+        }
+        let cap = self.max_mines_per_cell;
+        let square = &mut self[(x, y)];
+        match square.state {
+            SquareState::Revealed => return Err("Square has already been revealed"),
+            SquareState::Void => return Err(VOID_SQUARE_ERROR),
+            SquareState::Flagged if square.flags >= cap => {
+                square.state = SquareState::Hidden;
+                square.flags = 0;
+            }
+            _ => {
+                square.state = SquareState::Flagged;
+                square.flags += 1;
+            }
+        }
+        Ok(square.flags)
+    }
+
+    /// Chords on a revealed number at the given (x, y) cords. If the number of flags around the
+    /// square matches its number then all of the other hidden neighbours are revealed
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare, RevealOutcome};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 1);
+    /// let mut mine = MinesweeperSquare::new();
+    /// mine.set_is_mine(true);
+    /// board.set(0, 0, mine);
+    ///
+    /// board.reveal(1, 0).unwrap();
+    /// board.toggle_flag(0, 0).unwrap();
+    /// assert_eq!(board.chord(1, 0), Ok(RevealOutcome::Revealed(1)));
+    /// ```
+    pub fn chord(&mut self, x: usize, y: usize) -> Result<RevealOutcome, &'static str> {
+        if x >= self.width || y >= self.height {
+            return Err("Square selected is out of bounds");
+        }
+        if self[(x, y)].state != SquareState::Revealed {
+            return Err("Only revealed squares can be chorded");
+        }
+        let mut flags = 0;
+        let mut hidden = Vec::new();
+        for (x_index, y_index) in self.neighbours(x, y) {
+            match self[(x_index, y_index)].state {
+                SquareState::Flagged => flags += self[(x_index, y_index)].flags as isize,
+                SquareState::Hidden => hidden.push((x_index, y_index)),
+                SquareState::Revealed | SquareState::Void => {}
+            }
+        }
+        if flags != self[(x, y)].value as isize {
+            return Err("Number of flags around the square does not match its number");
+        }
+        if hidden.is_empty() {
+            return Err("No hidden squares around the square to reveal");
+        }
+
+        let mut revealed = 0;
+        let mut hit_mine = false;
+        for (x_index, y_index) in hidden {
+            if self[(x_index, y_index)].is_mine() {
+                self[(x_index, y_index)].state = SquareState::Revealed;
+                hit_mine = true;
+            } else {
+                revealed += self.update_board(x_index, y_index);
+            }
+        }
+        if hit_mine {
+            return Ok(RevealOutcome::Mine);
+        }
+        self.flag_remaining_mines();
+        Ok(RevealOutcome::Revealed(revealed))
+    }
+
+    /// Reveals the square at the given (x, y) cords for a player in Minesweeper Flags. Mines are not
+    /// lost on: a revealed mine is claimed by the player and shown with their mark.
+    /// Returns RevealOutcome::Mine if a mine was claimed, otherwise the number of squares opened
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare, RevealOutcome};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 1);
+    /// let mut mine = MinesweeperSquare::new();
+    /// mine.set_is_mine(true);
+    /// board.set(0, 0, mine);
+    ///
+    /// assert_eq!(board.claim(0, 0, 1), Ok(RevealOutcome::Mine));
+    /// assert_eq!(board.get(0, 0).unwrap().get_owner(), Some(1));
+    /// assert!(board.claim(0, 0, 0).is_err());
+    /// ```
+    pub fn claim(
+        &mut self,
+        x: usize,
+        y: usize,
+        player: usize,
+    ) -> Result<RevealOutcome, &'static str> {
+        if x >= self.width || y >= self.height {
+            return Err("Square selected is out of bounds");
+        }
+        let player = u8::try_from(player).map_err(|_| "Player number is too large")?;
+        match self[(x, y)].state {
+            SquareState::Hidden => {}
+            SquareState::Void => return Err(VOID_SQUARE_ERROR),
+            _ => return Err("Square has already been revealed"),
+        }
+        if self[(x, y)].is_mine() {
+            self[(x, y)].state = SquareState::Revealed;
+            self[(x, y)].owner = Some(player);
+            return Ok(RevealOutcome::Mine);
+        }
+        Ok(RevealOutcome::Revealed(self.update_board(x, y)))
+    }
+
+    /// Gets input from the user and makes the given move. Returns the (x, y) cords of the square selected
     pub fn make_move(&mut self) -> Result<(usize, usize), &'static str> {
-        let mut move_made = false;
-        while !move_made {
-            match handle_input(self.width, self.height) {
-                Ok((row_index, col_index)) => {
-                    if self.board[row_index][col_index].is_mine {
-                        return Err("You lose");
-                    }
-                    if self.board[row_index][col_index].state == SquareState::Hidden {
-                        self.update_board(col_index, row_index);
-                        move_made = true;
-                    } else {
-                        println!("Invalid position selection. Please select a non selected square");
+        loop {
+            match handle_input(self.width, self.height, self.neighbourhood) {
+                Ok((row_index, col_index)) => match self.reveal(col_index, row_index) {
+                    Ok(RevealOutcome::Mine) => return Err("You lose"),
+                    Ok(RevealOutcome::Revealed(_)) => return Ok((col_index, row_index)),
+                    Err(e) => {
+                        println!("Invalid position selection. {}", e);
                         continue;
                     }
-                }
+                },
                 Err(e) => {
                     println!("{}", e);
                     continue;
                 }
             }
         }
-        Ok((0, 0))
     }
 
-    /// Handles marking a square as flagged. If invalid square gotten from user input is not able to be marked it will alert the user. Returning a result Ok() if successful.
+    /// Handles marking a square as flagged (or removing the flag if it is already flagged, or adding another on boards where squares hold several mines). If invalid square gotten from user input is not able to be marked it will alert the user. Returning a result Ok() if successful.
     pub fn mark_square(&mut self) -> Result<(), &'static str> {
-        let mut move_made = false;
-        while !move_made {
-            match handle_input(self.width, self.height) {
-                Ok((row_index, col_index)) => {
-                    if self.board[row_index][col_index].state == SquareState::Hidden {
-                        self.board[row_index][col_index].state = SquareState::Flagged;
-                        move_made = true;
-                    } else {
-                        println!("Invalid position selection. Please select a non selected square to mark/flag");
+        loop {
+            match handle_input(self.width, self.height, self.neighbourhood) {
+                Ok((row_index, col_index)) => match self.cycle_flag(col_index, row_index) {
+                    Ok(_) => return Ok(()),
+                    Err(e) => {
+                        println!("Invalid position selection. {}", e);
                         continue;
                     }
+                },
+                Err(e) => {
+                    println!("{}", e);
+                    continue;
                 }
+            }
+        }
+    }
+
+    /// Gets input from the user and chords on the selected square. Returns an Err("You lose") if a mine was revealed
+    pub fn chord_square(&mut self) -> Result<(usize, usize), &'static str> {
+        loop {
+            match handle_input(self.width, self.height, self.neighbourhood) {
+                Ok((row_index, col_index)) => match self.chord(col_index, row_index) {
+                    Ok(RevealOutcome::Mine) => return Err("You lose"),
+                    Ok(RevealOutcome::Revealed(_)) => return Ok((col_index, row_index)),
+                    Err(e) => {
+                        println!("Invalid position selection. {}", e);
+                        continue;
+                    }
+                },
                 Err(e) => {
                     println!("{}", e);
                     continue;
                 }
             }
         }
-        Ok(())
     }
 
-    /// returns Some(()) if the board state is won. Used to terminate the game
+    /// returns Some(()) if the board state is won: every mine is flagged and no safe square is, so
+    /// flagging every square does not win. Used to terminate the game
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(2, 1);
+    /// let mut mine = MinesweeperSquare::new();
+    /// mine.set_is_mine(true);
+    /// board.set(0, 0, mine);
+    ///
+    /// board.toggle_flag(0, 0).unwrap();
+    /// board.toggle_flag(1, 0).unwrap();
+    /// assert!(board.is_won().is_none());
+    /// board.toggle_flag(1, 0).unwrap();
+    /// assert!(board.is_won().is_some());
+    /// ```
     pub fn is_won(&self) -> Option<()> {
-        for row in self.board.iter() {
-            for square in row.iter() {
-                if square.state != SquareState::Flagged && square.is_mine {
-                    return None;
+        self.board
+            .iter()
+            .all(|square| square.flags == square.mines)
+            .then_some(())
+    }
+
+    /// Returns true once every square that is not a mine has been revealed
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(2, 2);
+    ///
+    /// assert!(!board.is_cleared());
+    /// board.reveal(0, 0).unwrap();
+    /// assert!(board.is_cleared());
+    /// ```
+    pub fn is_cleared(&self) -> bool {
+        self.board.iter().all(|square| {
+            square.is_mine() || matches!(square.state, SquareState::Revealed | SquareState::Void)
+        })
+    }
+
+    /// Returns the number of mines on the board
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(10, 10);
+    /// board.increase_difficulty();
+    ///
+    /// assert_eq!(board.mine_count(), 10);
+    /// ```
+    pub fn mine_count(&self) -> usize {
+        self.board.iter().map(|square| square.mines as usize).sum()
+    }
+
+    /// Returns the 3BV (Bechtel's Board Benchmark Value) of the board. This is the minimum number of
+    /// clicks needed to clear the board without flagging: one per opening plus one for every safe
+    /// square that is not on the edge of an opening
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(5, 1);
+    /// let mut mine = MinesweeperSquare::new();
+    /// mine.set_is_mine(true);
+    /// board.set(2, 0, mine);
+    ///
+    /// // two openings, one on either side of the mine
+    /// assert_eq!(board.three_bv(), 2);
+    /// ```
+    pub fn three_bv(&self) -> usize {
+        self.three_bv_counts().0
+    }
+
+    /// Returns how much of the board's 3BV has been cleared so far. An opening counts once any of
+    /// its empty squares are revealed and every other safe square counts once it is revealed
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(5, 1);
+    /// let mut mine = MinesweeperSquare::new();
+    /// mine.set_is_mine(true);
+    /// board.set(2, 0, mine);
+    ///
+    /// board.reveal(0, 0).unwrap();
+    /// assert_eq!(board.three_bv_solved(), 1);
+    /// ```
+    pub fn three_bv_solved(&self) -> usize {
+        self.three_bv_counts().1
+    }
+
+    /// Walks the openings of the board returning (3BV, 3BV solved)
+    fn three_bv_counts(&self) -> (usize, usize) {
+        // void squares are not part of the board, so they are never counted
+        let mut counted: Vec<bool> = self
+            .board
+            .iter()
+            .map(|square| square.state == SquareState::Void)
+            .collect();
+        let mut total = 0;
+        let mut solved = 0;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if counted[y * self.width + x]
+                    || self[(x, y)].is_mine()
+                    || self.check_square(x, y) != 0
+                {
+                    continue;
+                }
+                total += 1;
+                let mut opened = false;
+                let mut stack = vec![(x, y)];
+                counted[y * self.width + x] = true;
+                while let Some((x, y)) = stack.pop() {
+                    if self.check_square(x, y) != 0 {
+                        continue;
+                    }
+                    opened |= self[(x, y)].state == SquareState::Revealed;
+                    for (x_index, y_index) in self.neighbours(x, y) {
+                        if !counted[y_index * self.width + x_index] {
+                            counted[y_index * self.width + x_index] = true;
+                            stack.push((x_index, y_index));
+                        }
+                    }
+                }
+                if opened {
+                    solved += 1;
+                }
+            }
+        }
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if counted[y * self.width + x] || self[(x, y)].is_mine() {
+                    continue;
+                }
+                total += 1;
+                if self[(x, y)].state == SquareState::Revealed {
+                    solved += 1;
                 }
             }
         }
-        Some(())
+        (total, solved)
     }
 }
 
+/// Most mines a square can hold, so the numbers of the largest neighbourhood still fit in a u8
+const MAX_MINES_PER_CELL: u8 = 10;
+/// Error for a move on a square that is not part of a shaped board
+const VOID_SQUARE_ERROR: &str = "Square is not part of the board";
 const EMPTY_SQUARE: char = '\u{25FB}';
 const MARKED_SQUARE: char = '\u{1F6A9}';
+/// Marks for mines claimed by each player in Minesweeper Flags
+pub const PLAYER_MARKS: [char; 2] = ['\u{1F534}', '\u{1F535}'];
+
+/// Mixes the bits of a number, the finalizer of the SplitMix64 generator. Used to derive one
+/// seed from another, such as a chunk's seed in endless mode or the daily challenge's from a date
+pub(crate) fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Writes the symbol for a single square, based on its state and value
+pub(crate) fn write_square(f: &mut fmt::Formatter, square: &MinesweeperSquare) -> fmt::Result {
+    match square.state {
+        SquareState::Hidden => {
+            if square.value != -1 {
+                write!(f, "{}", square.value)
+            } else {
+                write!(f, "{}", EMPTY_SQUARE)
+            }
+        }
+        SquareState::Revealed => {
+            if let Some(owner) = square.owner {
+                write!(f, "{}", PLAYER_MARKS[owner as usize % PLAYER_MARKS.len()])
+            } else if square.is_mine() {
+                write!(f, "*")
+            } else {
+                write!(f, "{}", square.value)
+            }
+        }
+        SquareState::Flagged if square.flags > 1 => write!(f, "{}{}", MARKED_SQUARE, square.flags),
+        SquareState::Flagged => write!(f, "{}", MARKED_SQUARE),
+        SquareState::Void => write!(f, " "),
+    }
+}
 
 /// Implementation for fmt::Display for the board
 /// displays the given value for the item in each cord with 0..width and 0..height numbers and letters respectively
 impl fmt::Display for Board<MinesweeperSquare> {
     /// fmt function that reflects the debug print. Allows for printing in a human understandable way
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, " ")?;
-        for i in 0..self.board[0].len() {
-            if i >= 10 {
-                write!(f, "{}  ", i + 1)?;
-            } else {
-                write!(f, " {}  ", i + 1)?;
-            }
+        if self.neighbourhood == Neighbourhood::Hex {
+            return hex::render(self, f);
         }
-        writeln!(f)?;
+        write_column_numbers(f, self.width, self.height)?;
 
-        for (i, row) in self.board.iter().enumerate() {
-            let row_label = (b'a' + i as u8) as char;
-            write!(f, "{} ", row_label)?;
+        for (i, row) in self.iter().enumerate() {
+            write_row_label(f, i, self.height)?;
 
             for (j, square) in row.iter().enumerate() {
-                if j > 0 {
+                // the separator between two void squares is left out so cut away areas are blank
+                if j > 0 && (!square.is_void() || !row[j - 1].is_void()) {
                     write!(f, " | ")?;
+                } else if j > 0 {
+                    write!(f, "   ")?;
                 }
 
-                // Display logic based on the state and value of MinesweeperSquare
-                match square.state {
-                    SquareState::Hidden => {
-                        if square.value != -1 {
-                            write!(f, "{}", square.value)?
-                        } else {
-                            write!(f, "{}", EMPTY_SQUARE)?
-                        }
-                    }
-                    SquareState::Revealed => {
-                        if square.is_mine {
-                            write!(f, "*")?;
-                        } else {
-                            write!(f, "{}", square.value)?;
-                        }
-                    }
-                    SquareState::Flagged => write!(f, "{}", MARKED_SQUARE)?,
+                write_square(f, square)?;
+            }
+            // a toroidal board shows its right edge joining back on to the left
+            match self.topology {
+                Topology::Bounded if row.last().is_some_and(|square| square.is_void()) => {
+                    writeln!(f)?
                 }
+                Topology::Bounded => writeln!(f, " |")?,
+                Topology::Toroidal => writeln!(f, " ~ {}", row_label(i))?,
             }
-            writeln!(f, " |")?;
+        }
+        if self.topology == Topology::Toroidal {
+            writeln!(f, "~ edges wrap around to the opposite side")?;
         }
         Ok(())
     }
 }
 
-/// Helper function to handle input from the user to be used for making a move
-fn handle_input(max_width: usize, max_height: usize) -> Result<(usize, usize), &'static str> {
-    let row = input!("Enter row selection (must be char): ");
-    let row_index = match row.trim().bytes().next() {
-        Some(byte) if byte.is_ascii_lowercase() => (byte - b'a').into(),
-        _ => return Err("Invalid row selection. Please enter a character from 'a' to 'z'."),
+/// Menu shown when choosing which board to play
+pub const PRESET_MENU: &str = "Select a board\n1. Beginner (9x9, 10 mines)\n2. Intermediate (16x16, 40 mines)\n3. Expert (30x16, 99 mines)\n4. Custom\n";
+/// Menu shown before each move
+pub const ACTION_MENU: &str =
+    "What would you like to do?\n1. Mark/Flag a spot\n2. Select a spot\n3. Chord on a number\n";
+/// Prompt for the row of a square
+pub const ROW_PROMPT: &str = "Enter row selection (must be char): ";
+/// Prompt for the column of a square
+pub const COLUMN_PROMPT: &str = "Enter column selection (must be num): ";
+
+/// Returns the letters labelling the row: a to z, then aa, ab and so on like spreadsheet columns
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::row_label;
+///
+/// assert_eq!(row_label(0), "a");
+/// assert_eq!(row_label(25), "z");
+/// assert_eq!(row_label(26), "aa");
+/// assert_eq!(row_label(701), "zz");
+/// ```
+pub fn row_label(index: usize) -> String {
+    let mut letters = Vec::new();
+    let mut rest = index + 1;
+    while rest > 0 {
+        rest -= 1;
+        letters.push(b'a' + (rest % 26) as u8);
+        rest /= 26;
+    }
+    letters.iter().rev().map(|&letter| letter as char).collect()
+}
+
+/// Reads a row label written by row_label back into the row's index
+pub(crate) fn parse_row_label(label: &str) -> Option<usize> {
+    if label.is_empty() {
+        return None;
+    }
+    label
+        .bytes()
+        .try_fold(0usize, |index, byte| {
+            if !byte.is_ascii_lowercase() {
+                return None;
+            }
+            index
+                .checked_mul(26)?
+                .checked_add(usize::from(byte - b'a') + 1)
+        })
+        .map(|index| index - 1)
+}
+
+/// Writes the labels of every row of a board of the given height padded to the same width,
+/// followed by a space, so the squares after them line up
+pub(crate) fn write_row_label(f: &mut fmt::Formatter, index: usize, height: usize) -> fmt::Result {
+    let width = row_label(height.saturating_sub(1)).len();
+    write!(f, "{:<width$} ", row_label(index), width = width)
+}
+
+/// Writes the line of column numbers above a square grid of the given size, indented past the
+/// row labels
+pub(crate) fn write_column_numbers(
+    f: &mut fmt::Formatter,
+    width: usize,
+    height: usize,
+) -> fmt::Result {
+    write!(
+        f,
+        "{:width$}",
+        "",
+        width = row_label(height.saturating_sub(1)).len()
+    )?;
+    for i in 0..width {
+        if i >= 10 {
+            write!(f, "{}  ", i + 1)?;
+        } else {
+            write!(f, " {}  ", i + 1)?;
+        }
+    }
+    writeln!(f)
+}
+
+/// Parses the row letters and column number typed by the user into (row, column) indexes on a
+/// board of the given size
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::parse_square;
+///
+/// assert_eq!(parse_square("b", "3", 9, 9), Ok((1, 2)));
+/// assert_eq!(parse_square("ab", "1", 9, 30), Ok((27, 0)));
+/// assert!(parse_square("z", "3", 9, 9).is_err());
+/// assert!(parse_square("b", "0", 9, 9).is_err());
+/// ```
+pub fn parse_square(
+    row: &str,
+    col: &str,
+    max_width: usize,
+    max_height: usize,
+) -> Result<(usize, usize), &'static str> {
+    let Some(row_index) = parse_row_label(row.trim()) else {
+        return Err("Invalid row selection. Please enter the row's letters, such as 'a' or 'ab'.");
     };
 
-    let col = input!("Enter column selection (must be num): ");
     let col_index = match col.trim().parse::<usize>() {
         Ok(num) if num > 0 => num - 1,
         _ => return Err("Invalid column selection. Please enter a positive number."),
@@ -479,6 +1781,22 @@ fn handle_input(max_width: usize, max_height: usize) -> Result<(usize, usize), &
     }
 }
 
+/// Helper function to handle input from the user to be used for making a move. Hex boards take
+/// a single hex cord rather than a row and a column
+fn handle_input(
+    max_width: usize,
+    max_height: usize,
+    neighbourhood: Neighbourhood,
+) -> Result<(usize, usize), &'static str> {
+    if neighbourhood == Neighbourhood::Hex {
+        let cord = input!("{}", hex::CORD_PROMPT);
+        return hex::parse_cord(&cord, max_width, max_height);
+    }
+    let row = input!("{}", ROW_PROMPT);
+    let col = input!("{}", COLUMN_PROMPT);
+    parse_square(&row, &col, max_width, max_height)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -488,4 +1806,126 @@ mod tests {
         assert_eq!(board.width, 10);
         assert_eq!(board.height, 10);
     }
+
+    #[test]
+    fn test_default_board_is_playable() {
+        let mut board: Board<MinesweeperSquare> = Board::default();
+        assert_eq!(board.iter().count(), 5);
+        assert!(board.to_string().contains("e "));
+        assert_eq!(board.reveal(4, 4), Ok(RevealOutcome::Revealed(25)));
+    }
+
+    #[test]
+    fn test_multi_mine_cells() {
+        let mut board = Board::isize_board(3, 1);
+        board.place_multi_mines(0, 2);
+        let mut mines = MinesweeperSquare::new();
+        mines.set_mines(2);
+        board.set(0, 0, mines);
+        assert_eq!(board.mine_count(), 2);
+
+        board.cycle_flag(0, 0).unwrap();
+        assert!(board.is_won().is_none());
+        board.cycle_flag(0, 0).unwrap();
+        assert!(board.is_won().is_some());
+        assert!(board.to_string().contains("\u{1F6A9}2"));
+
+        // the number next to the square counts both of its mines
+        board.cycle_flag(0, 0).unwrap();
+        board.reveal(2, 0).unwrap();
+        assert_eq!(board.get(1, 0).unwrap().get_value(), 2);
+        assert!(board.is_won().is_some());
+    }
+
+    #[test]
+    fn test_toroidal_neighbours_wrap_around() {
+        let board = Board::isize_board_with_topology(4, 4, Topology::Toroidal);
+        let mut corner: Vec<_> = board.neighbours(0, 0).collect();
+        corner.sort();
+        assert_eq!(
+            corner,
+            vec![
+                (0, 1),
+                (0, 3),
+                (1, 0),
+                (1, 1),
+                (1, 3),
+                (3, 0),
+                (3, 1),
+                (3, 3)
+            ]
+        );
+        // a 2x2 board reaches every other square once, not twice
+        let small = Board::isize_board_with_topology(2, 2, Topology::Toroidal);
+        assert_eq!(small.neighbours(0, 0).count(), 3);
+    }
+
+    #[test]
+    fn test_toroidal_flood_fill_crosses_edges() {
+        let mut board = Board::isize_board_with_topology(5, 5, Topology::Toroidal);
+        let mut mine = MinesweeperSquare::new();
+        mine.set_is_mine(true);
+        board.set(2, 2, mine);
+        // the corner is two squares away from the mine on a torus as well, so it opens every
+        // square that does not touch the mine
+        assert_eq!(board.reveal(0, 0), Ok(RevealOutcome::Revealed(24)));
+        assert_eq!(board.three_bv(), 1);
+        assert!(board.to_string().contains("~ edges wrap around"));
+    }
+
+    #[test]
+    fn test_counts_follow_relocated_mines() {
+        let mut board =
+            Board::isize_board_with_neighbourhood(8, 6, Topology::Toroidal, Neighbourhood::Hex);
+        board.place_multi_mines_seeded(60, 3, 5);
+        let clear: Vec<(usize, usize)> = board.neighbours(3, 3).chain([(3, 3)]).collect();
+        board.relocate_mines_seeded(&clear, 8).unwrap();
+        assert_eq!(board.mine_count(), 60);
+        assert!(clear.iter().all(|&cord| !board[cord].is_mine()));
+
+        let mut recounted = board.clone();
+        recounted.recount();
+        assert_eq!(board, recounted);
+        assert_eq!(board.mines_around(3, 3), Some(0));
+    }
+
+    #[test]
+    fn test_uncounted_mines_can_be_removed() {
+        let mut board = Board::isize_board(3, 3);
+        board[(1, 1)].set_is_mine(true);
+        board.set(1, 1, MinesweeperSquare::new());
+        assert_eq!(board.mines_around(0, 0), Some(0));
+        assert_eq!(board.mine_count(), 0);
+    }
+
+    #[test]
+    fn test_lowering_the_cap_keeps_placed_mines() {
+        let mut board = Board::isize_board(3, 3);
+        board.place_multi_mines_seeded(20, 3, 1);
+        board.place_multi_mines_seeded(5, 1, 2);
+        assert_eq!(board.max_mines_per_cell(), 3);
+        assert_eq!(board.mine_count(), 25);
+
+        // squares given more mines than the cap, and more mines than the board has room for
+        let mut board = Board::isize_board(3, 3);
+        let mut mines = MinesweeperSquare::new();
+        mines.set_mines(2);
+        for (x, y) in [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1)] {
+            board.set(x, y, mines);
+        }
+        board.place_mines_seeded(3, 4);
+        assert_eq!(board.mine_count(), 10);
+        board.relocate_mines_seeded(&[(1, 1)], 5).unwrap();
+        assert_eq!(board.get(1, 1).unwrap().get_mines(), 0);
+        assert_eq!(board.mine_count(), 10);
+    }
+
+    #[test]
+    fn test_relocation_needs_room() {
+        let mut board = Board::isize_board(3, 3);
+        board.place_mines_seeded(8, 2);
+        let before = board.clone();
+        assert!(board.relocate_mines(&[(0, 0), (1, 1)]).is_err());
+        assert_eq!(board, before);
+    }
 }
@@ -1,18 +1,596 @@
 use prompted::input;
+use rusty_mine_sweeper::date::Date;
+use rusty_mine_sweeper::mask::Mask;
+use rusty_mine_sweeper::report::{ClickCounts, GameReport};
+use rusty_mine_sweeper::scores::{HighScores, ScoreEntry, ScoreTable};
+use rusty_mine_sweeper::stats::Statistics;
 use rusty_mine_sweeper::*;
+use std::net::TcpListener;
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 const MAX_HEIGHT: usize = 99;
 const MAX_WIDTH: usize = 99;
+/// Squares shown of an endless board at once
+const VIEW_WIDTH: usize = 16;
+const VIEW_HEIGHT: usize = 10;
+/// Menu shown before each move of an endless game
+const ENDLESS_MENU: &str =
+    "What would you like to do?\n1. Mark/Flag a spot\n2. Select a spot\n3. Move the view\n4. Stop\n";
 
 enum UserAction {
     Mark,
     Select,
+    Chord,
 }
 
+const USAGE: &str = "Usage: rusty-mine-sweeper [command]
+
+Commands:
+    play                      Play a game of mine sweeper (default)
+    daily [options]           Play today's challenge, the same 16x16 board for everyone that day
+        --date <YYYY-MM-DD>       Play the challenge of an earlier day instead
+    load <file>               Play the board in a layout file (. safe, * mine, f/F flag, 0-8 revealed)
+                              or a .mbf board file
+    replay <file>             Step through a RAWVF replay one move at a time
+    puzzle [options]          Solve a puzzle that starts with clues and needs no guessing
+        --difficulty <name>       easy, medium or hard (default easy)
+        --size <width>x<height>   Size of the puzzle, at most 100 squares (default 8x8)
+        --mines <count>           Number of mines (default 12)
+        --seed <seed>             Seed of the puzzle (default random)
+    flags                     Play Minesweeper Flags, two players taking turns on one board
+    endless [options]         Play on a board without edges, scored by the area cleared
+        --seed <seed>             Seed of the board (default random)
+        --mines <count>           Mines in each 16x16 chunk, 40 to 128 (default 50)
+    stats                     Print your statistics from previous games
+    scores [preset]           Print the high score tables
+    scores reset [preset]     Clear the high score tables
+    bot [options]             Benchmark the bot strategies against many generated boards
+        --strategy <name|all>     random, deduction or probability (default all)
+        --preset <name|all>       Board preset to play (default all)
+        --size <width>x<height>   Play a custom board size instead of a preset
+        --mines <count>           Number of mines on a custom board
+        --games <count>           Games played per strategy and board (default 1000)
+        --seed <seed>             Seed of the first board (default 0)
+    serve [options]           Play in the browser on a local web server
+        --port <port>             Port to listen on (default 8080)
+        --preset <name>           Board preset of the first game (default beginner)
+    tcp-serve [options]       Serve games over plain TCP, playable with netcat or telnet
+        --port <port>             Port to listen on (default 2323)
+        --idle-timeout <seconds>  Disconnect players idle for this long (default 300)
+    race host [options]       Host a race between two players on identical boards
+        --port <port>             Port to listen on (default 2324)
+        --preset <name>           Board preset to race on (default beginner)
+        --seed <seed>             Seed of the board (default random)
+    race join [address]       Join a race (default 127.0.0.1:2324), or connect with netcat";
+
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None | Some("play") => play(),
+        Some("daily") => {
+            if let Err(e) = daily_command(&args[1..]) {
+                println!("{}\n\n{}", e, USAGE);
+            }
+        }
+        Some("load") => match args.get(1) {
+            Some(path) => play_layout(Path::new(path)),
+            None => println!("{}", USAGE),
+        },
+        Some("replay") => match args.get(1) {
+            Some(path) => play_replay(Path::new(path)),
+            None => println!("{}", USAGE),
+        },
+        Some("puzzle") => {
+            if let Err(e) = puzzle_command(&args[1..]) {
+                println!("{}\n\n{}", e, USAGE);
+            }
+        }
+        Some("flags") => play_flags(),
+        Some("endless") => {
+            if let Err(e) = endless_command(&args[1..]) {
+                println!("{}\n\n{}", e, USAGE);
+            }
+        }
+        Some("stats") => print_stats(),
+        Some("scores") => scores_command(&args[1..]),
+        Some("bot") => {
+            if let Err(e) = bot_command(&args[1..]) {
+                println!("{}\n\n{}", e, USAGE);
+            }
+        }
+        Some("serve") => {
+            if let Err(e) = serve_command(&args[1..]) {
+                println!("{}\n\n{}", e, USAGE);
+            }
+        }
+        Some("tcp-serve") => {
+            if let Err(e) = tcp_serve_command(&args[1..]) {
+                println!("{}\n\n{}", e, USAGE);
+            }
+        }
+        Some("race") => {
+            if let Err(e) = race_command(&args[1..]) {
+                println!("{}\n\n{}", e, USAGE);
+            }
+        }
+        Some(_) => println!("{}", USAGE),
+    }
+}
+
+/// Plays a single interactive game and records the result in the statistics
+fn play() {
     println!("Welcome to rusty mine sweeper by Nathan Moes! Please note that all mines MUST be marked/flagged in order to win the game");
-    let width: usize;
-    let height: usize;
+    let (board, preset) = get_board();
+    let category = stats_category(&board);
+    let report = play_game(board);
+    println!("{}", report);
+    record_stats(&report, category);
+    if let Some((preset, seed)) = preset {
+        record_high_score(&report, preset, seed);
+    }
+}
+
+/// Plays the board saved in a layout file, or an MBF file if it ends in .mbf. The game is not
+/// recorded, as the board may be part way through or made up by hand
+fn play_layout(path: &Path) {
+    let board = if path.extension().is_some_and(|extension| extension == "mbf") {
+        mbf::load(path)
+    } else {
+        layout::load(path)
+    };
+    match board {
+        Ok(board) => println!("{}", play_game(board)),
+        Err(e) => println!("Could not load {}: {}", path.display(), e),
+    }
+}
+
+/// Plays the daily challenge of today or the given date, recorded in its own statistics
+fn daily_command(args: &[String]) -> Result<(), String> {
+    let today = Date::today();
+    let date = match option_value(args, "--date") {
+        Some(date) => date.parse().map_err(String::from)?,
+        None => today,
+    };
+    daily::check_date(date, today)?;
+
+    println!(
+        "Daily challenge for {}: {} board with seed {}",
+        date,
+        daily::PRESET.name(),
+        daily::seed(date)
+    );
+    let report = play_game(daily::board(date));
+    println!("{}", report);
+    record_stats(&report, Some(daily::CATEGORY.to_string()));
+    Ok(())
+}
+
+/// Generates a puzzle and plays it. Puzzles are not recorded in the statistics
+fn puzzle_command(args: &[String]) -> Result<(), String> {
+    let difficulty = match option_value(args, "--difficulty") {
+        Some(name) => puzzle::Difficulty::from_name(name)
+            .ok_or_else(|| format!("Unknown difficulty {}", name))?,
+        None => puzzle::Difficulty::Easy,
+    };
+    let (mut width, mut height, mines) = puzzle::DEFAULT_SIZE;
+    if let Some(size) = option_value(args, "--size") {
+        (width, height) = parse_size(size)?;
+    }
+    let mines = parse_option(args, "--mines", mines)?;
+    let seed: u64 = parse_option(args, "--seed", rand::random())?;
+
+    println!("Generating a puzzle...");
+    let puzzle = puzzle::generate(width, height, mines, difficulty, seed).map_err(String::from)?;
+    println!(
+        "A {} puzzle with seed {}. It starts with {} squares revealed and every mine can be found from them without guessing",
+        difficulty.name(),
+        seed,
+        puzzle.clues()
+    );
+    println!("{}", play_game(puzzle.board));
+    Ok(())
+}
+
+/// Shows a replay move by move, waiting for the user between moves
+fn play_replay(path: &Path) {
+    let replay = match rawvf::Replay::load(path) {
+        Ok(replay) => replay,
+        Err(e) => {
+            println!("Could not load {}: {}", path.display(), e);
+            return;
+        }
+    };
+    for (key, value) in &replay.header {
+        println!("{}: {}", key, value);
+    }
+    let mut playback = replay.playback();
+    println!("{}", playback.board());
+    while input!("Press enter for the next move, or q to stop\n").trim() != "q" {
+        let Some(event) = playback.step() else {
+            println!("End of the replay");
+            break;
+        };
+        if let Some((x, y)) = event.square {
+            println!(
+                "{:.2}s {} {}{}",
+                event.time,
+                event.kind,
+                row_label(y),
+                x + 1
+            );
+        }
+        println!("{}", playback.board());
+    }
+}
+
+/// Plays a hot-seat game of Minesweeper Flags between two players at the same terminal
+fn play_flags() {
+    println!("Minesweeper Flags! Take turns revealing squares. Finding a mine scores a point and gives you another turn. The first to find more than half of the mines wins");
+    let (width, height, mines) = flags::CLASSIC_SIZE;
+    let mut board: Board<MinesweeperSquare> = Board::isize_board(width, height);
+    board.place_mines(mines);
+    let mut game = flags::FlagsGame::new(board);
+    while !game.is_over() {
+        let [first, second] = game.scores();
+        println!(
+            "{}\nScore: player 1 {} - {} player 2\nPlayer {} ({}) to move",
+            game.board,
+            first,
+            second,
+            game.turn() + 1,
+            PLAYER_MARKS[game.turn()]
+        );
+        let row = input!("{}", ROW_PROMPT);
+        let col = input!("{}", COLUMN_PROMPT);
+        let (row_index, col_index) = match parse_square(&row, &col, width, height) {
+            Ok(square) => square,
+            Err(e) => {
+                println!("{}", e);
+                continue;
+            }
+        };
+        match game.take_turn(col_index, row_index) {
+            Ok(flags::TurnOutcome::Claimed) => println!("Mine found! Go again"),
+            Ok(flags::TurnOutcome::Revealed(_)) => {}
+            Err(e) => println!("Invalid position selection. {}", e),
+        }
+    }
+    let [first, second] = game.scores();
+    println!(
+        "{}\nFinal score: player 1 {} - {} player 2",
+        game.board, first, second
+    );
+    match game.winner() {
+        Some(player) => println!("Player {} wins!", player + 1),
+        None => println!("It's a draw"),
+    }
+}
+
+/// Plays an endless game, moving a view around a board with no edges until a mine is hit or the
+/// player stops
+fn endless_command(args: &[String]) -> Result<(), String> {
+    let seed: u64 = parse_option(args, "--seed", rand::random())?;
+    let mines = parse_option(args, "--mines", infinite::DEFAULT_MINES_PER_CHUNK)?;
+    let mut board = infinite::InfiniteBoard::new(seed, mines).map_err(String::from)?;
+    println!("Endless mine sweeper with seed {}. The board goes on forever, so move the view to explore it. The square at x 0, y 0 in the middle is always safe. Your score is the number of squares you clear", seed);
+    let mut top_left = (-(VIEW_WIDTH as i64) / 2, -(VIEW_HEIGHT as i64) / 2);
+    loop {
+        let view = board.view(top_left, VIEW_WIDTH, VIEW_HEIGHT);
+        println!("{}Squares cleared: {}", view, board.area_cleared());
+        let action = input!("{}", ENDLESS_MENU);
+        match action.trim() {
+            choice @ ("1" | "2") => {
+                let row = input!("{}", ROW_PROMPT);
+                let col = input!("{}", COLUMN_PROMPT);
+                let cord = match parse_square(&row, &col, VIEW_WIDTH, VIEW_HEIGHT) {
+                    Ok((row_index, col_index)) => view.cord(col_index, row_index),
+                    Err(e) => {
+                        println!("{}", e);
+                        continue;
+                    }
+                };
+                let result = if choice == "1" {
+                    board.toggle_flag(cord).map(|_| ())
+                } else {
+                    board.reveal(cord).map(|_| ())
+                };
+                if let Err(e) = result {
+                    println!("Invalid position selection. {}", e);
+                }
+                if board.is_lost() {
+                    println!(
+                        "{}You hit a mine!",
+                        board.view(top_left, VIEW_WIDTH, VIEW_HEIGHT)
+                    );
+                    break;
+                }
+            }
+            "3" => {
+                let direction = input!("Move which way? w=up, a=left, s=down, d=right\n");
+                let (x, y) = match direction.trim() {
+                    "w" => (0, -1),
+                    "a" => (-1, 0),
+                    "s" => (0, 1),
+                    "d" => (1, 0),
+                    _ => {
+                        println!("Invalid input. Please enter w, a, s or d.");
+                        continue;
+                    }
+                };
+                // move half a view at a time so the squares just played stay in sight
+                top_left.0 += x * VIEW_WIDTH as i64 / 2;
+                top_left.1 += y * VIEW_HEIGHT as i64 / 2;
+            }
+            "4" => break,
+            _ => println!("Invalid input. Please enter 1, 2, 3 or 4."),
+        }
+    }
+    println!("You cleared {} squares", board.area_cleared());
+    Ok(())
+}
+
+/// Asks for the player's name and adds the game to the high scores if it was a fast enough win
+fn record_high_score(report: &GameReport, preset: Preset, seed: u64) {
+    let path = HighScores::default_path();
+    match HighScores::load_from(&path) {
+        Ok(scores) if report.won && scores.qualifies(preset, report.elapsed) => {}
+        Ok(_) => return,
+        Err(e) => {
+            println!("Could not load high scores: {}", e);
+            return;
+        }
+    }
+    let name = input!("New high score! Enter your name: ");
+    let entry = ScoreEntry::new(
+        &name,
+        report.elapsed,
+        Date::today(),
+        seed,
+        report.three_bv_per_second(),
+    );
+    match HighScores::update(&path, |scores| scores.insert(preset, entry)) {
+        Ok(Some(rank)) => println!("You placed #{} on the {} table", rank, preset.name()),
+        // Another run finishing at the same time may have pushed the time off the table
+        Ok(None) => println!("Your time did not make the {} table", preset.name()),
+        Err(e) => println!("Could not save high score: {}", e),
+    }
+}
+
+/// Prints or resets the high score tables
+fn scores_command(args: &[String]) {
+    let path = HighScores::default_path();
+    let (reset, preset_arg) = match args.first().map(String::as_str) {
+        Some("reset") => (true, args.get(1)),
+        _ => (false, args.first()),
+    };
+    let preset = match preset_arg {
+        Some(name) => match Preset::from_name(name) {
+            Some(preset) => Some(preset),
+            None => {
+                println!(
+                    "Unknown preset {}. Use beginner, intermediate or expert",
+                    name
+                );
+                return;
+            }
+        },
+        None => None,
+    };
+
+    if reset {
+        let which = preset.map_or("all".to_string(), |preset| preset.name().to_string());
+        let confirm = input!("Reset {} high scores? (y/n) ", which);
+        if !confirm.trim().eq_ignore_ascii_case("y") {
+            return;
+        }
+        match HighScores::update(&path, |scores| scores.reset(preset)) {
+            Ok(()) => println!("High scores reset"),
+            Err(e) => println!("Could not reset high scores: {}", e),
+        }
+        return;
+    }
+
+    match HighScores::load_from(&path) {
+        Ok(scores) => match preset {
+            Some(preset) => print!("{}", ScoreTable(preset, scores.table(preset))),
+            None => print!("{}", scores),
+        },
+        Err(e) => println!("Could not load high scores: {}", e),
+    }
+}
+
+/// Returns the statistics category of a board that is not an ordinary grid of squares, so that
+/// toroidal games and other neighbourhoods are kept apart from ordinary games of the same size
+fn stats_category(board: &Board<MinesweeperSquare>) -> Option<String> {
+    let mut kind = Vec::new();
+    if board.topology() == Topology::Toroidal {
+        kind.push("toroidal".to_string());
+    }
+    if board.neighbourhood() != Neighbourhood::King {
+        kind.push(board.neighbourhood().name().to_string());
+    }
+    if board.max_mines_per_cell() > 1 {
+        kind.push(format!("{}-mine", board.max_mines_per_cell()));
+    }
+    if board.is_shaped() {
+        kind.push("shaped".to_string());
+    }
+    if kind.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{} {}x{}",
+        kind.join(" "),
+        board.width,
+        board.height
+    ))
+}
+
+/// Adds the game to the saved statistics, warning the user if they could not be saved.
+/// Games are recorded under the category if one is given
+fn record_stats(report: &GameReport, category: Option<String>) {
+    let recorded = Statistics::update(&Statistics::default_path(), |stats| match category {
+        Some(category) => stats.record_as(&category, report),
+        None => stats.record(report),
+    });
+    if let Err(e) = recorded {
+        println!("Could not save statistics: {}", e);
+    }
+}
+
+fn print_stats() {
+    match Statistics::load() {
+        Ok(stats) => println!("{}", stats),
+        Err(e) => println!("Could not load statistics: {}", e),
+    }
+}
+
+/// Returns the value following the option in the arguments, e.g. "10" for --games 10
+fn option_value<'a>(args: &'a [String], option: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == option)
+        .and_then(|index| args.get(index + 1))
+        .map(String::as_str)
+}
+
+/// Parses a board size written as <width>x<height>, e.g. 9x9
+fn parse_size(size: &str) -> Result<(usize, usize), String> {
+    size.split_once('x')
+        .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+        .filter(|&(w, h): &(usize, usize)| w > 0 && h > 0)
+        .ok_or_else(|| format!("Invalid board size {}", size))
+}
+
+/// Parses the value following the option, using the default if the option was not given
+fn parse_option<T: std::str::FromStr>(
+    args: &[String],
+    option: &str,
+    default: T,
+) -> Result<T, String> {
+    match option_value(args, option) {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("Invalid value {} for {}", value, option)),
+        None => Ok(default),
+    }
+}
+
+/// Plays many games headlessly with each of the chosen bot strategies and board configurations
+fn bot_command(args: &[String]) -> Result<(), String> {
+    let games: usize = parse_option(args, "--games", 1000)?;
+    let seed: u64 = parse_option(args, "--seed", 0)?;
+
+    let strategies: Vec<&str> = match option_value(args, "--strategy").unwrap_or("all") {
+        "all" => bot::STRATEGY_NAMES.to_vec(),
+        name if bot::strategy_by_name(name).is_some() => vec![name],
+        name => return Err(format!("Unknown strategy {}", name)),
+    };
+
+    let boards: Vec<(usize, usize, usize)> = if let Some(size) = option_value(args, "--size") {
+        let (width, height) = parse_size(size)?;
+        let mines = parse_option(args, "--mines", width * height / 10)?;
+        if mines >= width * height {
+            return Err("A board needs at least one square without a mine".to_string());
+        }
+        vec![(width, height, mines)]
+    } else {
+        match option_value(args, "--preset").unwrap_or("all") {
+            "all" => Preset::ALL.iter().map(Preset::dimensions).collect(),
+            name => match Preset::from_name(name) {
+                Some(preset) => vec![preset.dimensions()],
+                None => return Err(format!("Unknown preset {}", name)),
+            },
+        }
+    };
+
+    println!("{}", bot::BENCHMARK_HEADER);
+    for (width, height, mines) in boards {
+        for name in strategies.iter() {
+            let mut strategy = bot::strategy_by_name(name).expect("strategy names are checked");
+            let config = bot::BenchmarkConfig {
+                width,
+                height,
+                mines,
+                games,
+                seed,
+            };
+            println!("{}", bot::benchmark(strategy.as_mut(), config));
+        }
+    }
+    Ok(())
+}
+
+/// Runs the HTML web server on localhost
+fn serve_command(args: &[String]) -> Result<(), String> {
+    let port: u16 = parse_option(args, "--port", 8080)?;
+    let preset = match option_value(args, "--preset") {
+        Some(name) => Preset::from_name(name).ok_or_else(|| format!("Unknown preset {}", name))?,
+        None => Preset::Beginner,
+    };
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .map_err(|e| format!("Could not listen on port {}: {}", port, e))?;
+    println!("Serving mine sweeper on http://127.0.0.1:{}/", port);
+    web::serve(listener, preset).map_err(|e| e.to_string())
+}
+
+/// Runs the line based TCP server on localhost
+fn tcp_serve_command(args: &[String]) -> Result<(), String> {
+    let port: u16 = parse_option(args, "--port", 2323)?;
+    let idle_timeout: u64 =
+        parse_option(args, "--idle-timeout", tcp::DEFAULT_IDLE_TIMEOUT.as_secs())?;
+    if idle_timeout == 0 {
+        return Err("The idle timeout must be at least 1 second".to_string());
+    }
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .map_err(|e| format!("Could not listen on port {}: {}", port, e))?;
+    println!(
+        "Serving mine sweeper on 127.0.0.1:{}, connect with nc 127.0.0.1 {}",
+        port, port
+    );
+    tcp::serve(listener, Duration::from_secs(idle_timeout)).map_err(|e| e.to_string())
+}
+
+/// Hosts or joins a two player race
+fn race_command(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("host") => {
+            let port: u16 = parse_option(args, "--port", 2324)?;
+            let seed: u64 = parse_option(args, "--seed", rand::random())?;
+            let preset = match option_value(args, "--preset") {
+                Some(name) => {
+                    Preset::from_name(name).ok_or_else(|| format!("Unknown preset {}", name))?
+                }
+                None => Preset::Beginner,
+            };
+            let listener = TcpListener::bind(("127.0.0.1", port))
+                .map_err(|e| format!("Could not listen on port {}: {}", port, e))?;
+            println!(
+                "Waiting for two players. Join with cargo run -r -- race join 127.0.0.1:{} or nc 127.0.0.1 {}",
+                port, port
+            );
+            let winner = race::host(&listener, preset, seed, tcp::DEFAULT_IDLE_TIMEOUT)
+                .map_err(|e| e.to_string())?;
+            println!("Player {} won the race", winner + 1);
+            Ok(())
+        }
+        Some("join") => {
+            let address = args.get(1).map_or("127.0.0.1:2324", String::as_str);
+            race::join(address).map_err(|e| format!("Could not join {}: {}", address, e))
+        }
+        _ => Err("Use race host or race join".to_string()),
+    }
+}
+
+/// Asks the user for a preset or a custom board and creates it.
+/// Preset boards are returned with the preset and the seed used to place their mines
+fn get_board() -> (Board<MinesweeperSquare>, Option<(Preset, u64)>) {
+    if let Some(preset) = get_user_preset() {
+        let seed: u64 = rand::random();
+        return (preset.board_with_seed(seed), Some((preset, seed)));
+    }
+    let mut width: usize;
+    let mut height: usize;
     loop {
         (width, height) = match get_params() {
             Ok((w, h)) => (w, h),
@@ -22,13 +600,36 @@ fn main() {
         };
         break;
     }
-    let mut score = 0;
-    let mut board: Board<MinesweeperSquare> = Board::isize_board(width, height);
+    let mut mask = get_user_shape(width, height);
+    // a shape file brings its own size
+    (width, height) = (mask.width, mask.height);
+    let neighbourhood = get_user_neighbourhood();
+    let topology = get_user_topology();
+    let mut board: Board<MinesweeperSquare> =
+        Board::isize_board_with_neighbourhood(width, height, topology, neighbourhood);
+    if board.height != height {
+        // wrapped hex boards get a row added to make the height even, which the shape needs too
+        mask = Mask::from_fn(width, board.height, |x, y| mask.contains(x, y));
+        println!(
+            "Wrapped hex boards need an even height, using {}",
+            board.height
+        );
+    }
+    if let Err(e) = board.apply_mask(&mask) {
+        println!("{}", e);
+    }
+    let mines_per_cell = get_user_mines_per_cell();
     if let Ok(x) = get_user_difficulty_level() {
-        for _ in 0..x {
-            board.increase_difficulty();
-        }
+        // each level adds 10% of the squares in mines, as increase_difficulty does
+        board.place_multi_mines(x * (board.square_count() / 10), mines_per_cell);
     }
+    (board, None)
+}
+
+/// Runs the game loop on the board until the game is won or lost, returning the end of game report
+fn play_game(mut board: Board<MinesweeperSquare>) -> GameReport {
+    let mut clicks = ClickCounts::default();
+    let mut started: Option<Instant> = None;
     println!("{}", board);
     loop {
         if board.is_won().is_some() {
@@ -37,48 +638,173 @@ fn main() {
         }
 
         match get_user_action() {
-            Ok(action) => match action {
-                UserAction::Mark => {
-                    loop {
-                        if board.mark_square().is_err() {
-                            println!("Invalid square to mark/flag. Please try again.");
-                            continue;
+            Ok(action) => {
+                started.get_or_insert_with(Instant::now);
+                match action {
+                    UserAction::Mark => {
+                        loop {
+                            if board.mark_square().is_err() {
+                                println!("Invalid square to mark/flag. Please try again.");
+                                continue;
+                            }
+                            break;
                         }
-                        break;
+                        clicks.flags += 1;
+                        println!("Board after your mark/flag:\n{}", board);
                     }
-                    println!("Board after your mark/flag:\n{}", board);
-                }
-                UserAction::Select => match board.make_move() {
-                    Ok(_) => {
-                        score += 1;
-                        println!("Board after your move:\n{}", board);
+                    UserAction::Select => {
+                        clicks.reveals += 1;
+                        match board.make_move() {
+                            Ok(_) => println!("Board after your move:\n{}", board),
+                            Err(x) => {
+                                if x == "You lose" {
+                                    println!("{}\nYou lose", board);
+                                    break;
+                                }
+                                println!("Invalid move");
+                            }
+                        }
                     }
-                    Err(x) => {
-                        if x == "You lose" {
-                            println!("You lose");
-                            break;
+                    UserAction::Chord => {
+                        clicks.chords += 1;
+                        match board.chord_square() {
+                            Ok(_) => println!("Board after your chord:\n{}", board),
+                            Err(x) => {
+                                if x == "You lose" {
+                                    println!("{}\nYou lose", board);
+                                    break;
+                                }
+                                println!("Invalid move");
+                            }
                         }
-                        println!("Invalid move");
                     }
-                },
-            },
+                }
+            }
             Err(_) => {
                 println!("Invalid choice. Please try again.");
                 continue;
             }
         }
     }
-    println!("Your score is {}", score);
+    let elapsed = started.map(|start| start.elapsed()).unwrap_or_default();
+    GameReport::new(&board, clicks, elapsed)
+}
+
+/// Asks the user which board to play. Returns None if they want a custom board
+fn get_user_preset() -> Option<Preset> {
+    loop {
+        let choice = input!("{}", PRESET_MENU);
+        match choice.trim() {
+            "1" => return Some(Preset::Beginner),
+            "2" => return Some(Preset::Intermediate),
+            "3" => return Some(Preset::Expert),
+            "4" => return None,
+            _ => {
+                println!("Invalid input. Please enter 1..4.");
+                continue;
+            }
+        }
+    }
+}
+
+/// Asks the user for the neighbourhood of a custom board
+fn get_user_neighbourhood() -> Neighbourhood {
+    loop {
+        let choice = input!("Select which squares numbers count mines in\n1. Surrounding 8 (normal)\n2. Up, down, left and right only\n3. A knight's move away\n4. Within 2 squares\n5. Surrounding 6 on a hex board\n");
+        match choice.trim() {
+            "1" => return Neighbourhood::King,
+            "2" => return Neighbourhood::Orthogonal,
+            "3" => return Neighbourhood::Knight,
+            "4" => return Neighbourhood::Radius2,
+            "5" => return Neighbourhood::Hex,
+            _ => {
+                println!("Invalid input. Please enter 1..5.");
+                continue;
+            }
+        }
+    }
+}
+
+/// Asks for the shape of the board, either one of the built in shapes at the given size or a
+/// shape file
+fn get_user_shape(width: usize, height: usize) -> Mask {
+    loop {
+        let choice = input!(
+            "Select the board shape\n1. Rectangle\n2. With a hole\n3. Cross\n4. Circle\n5. Load a shape file\n"
+        );
+        let mask = match choice.trim() {
+            "1" => Mask::rectangle(width, height),
+            "2" => Mask::with_hole(width, height),
+            "3" => Mask::cross(width, height),
+            "4" => Mask::circle(width, height),
+            "5" => {
+                let path = input!("Path to the shape file ('#' for a square, '.' for a gap): ");
+                match Mask::load(Path::new(path.trim())) {
+                    Ok(mask) if mask.width <= MAX_WIDTH && mask.height <= MAX_HEIGHT => mask,
+                    Ok(_) => {
+                        println!(
+                            "Shapes can be at most {} wide and {} tall.",
+                            MAX_WIDTH, MAX_HEIGHT
+                        );
+                        continue;
+                    }
+                    Err(e) => {
+                        println!("Could not load the shape: {}", e);
+                        continue;
+                    }
+                }
+            }
+            _ => {
+                println!("Invalid input. Please enter a number from 1 to 5.");
+                continue;
+            }
+        };
+        if mask.count() == 0 {
+            println!("That shape leaves no squares to play on, choose another.");
+            continue;
+        }
+        return mask;
+    }
+}
+
+/// Asks the user whether the edges of a custom board should wrap around
+fn get_user_topology() -> Topology {
+    loop {
+        let choice = input!("Select the board edges\n1. Normal\n2. Wrap around (toroidal)\n");
+        match choice.trim() {
+            "1" => return Topology::Bounded,
+            "2" => return Topology::Toroidal,
+            _ => {
+                println!("Invalid input. Please enter 1 or 2.");
+                continue;
+            }
+        }
+    }
 }
 
 fn get_user_action() -> Result<UserAction, &'static str> {
     loop {
-        let action = input!("What would you like to do?\n1. Mark/Flag a spot\n2. Select a spot\n");
+        let action = input!("{}", ACTION_MENU);
         match action.trim() {
             "1" => return Ok(UserAction::Mark),
             "2" => return Ok(UserAction::Select),
+            "3" => return Ok(UserAction::Chord),
             _ => {
-                println!("Invalid input. Please enter 1 or 2.");
+                println!("Invalid input. Please enter 1, 2 or 3.");
+                continue;
+            }
+        }
+    }
+}
+
+/// Asks the user for the most mines a square of a custom board can hold
+fn get_user_mines_per_cell() -> u8 {
+    loop {
+        let choice = input!("Select the most mines a square can hold, up to 9 (1 is normal). Flag a square once for each of its mines\n");
+        match choice.trim().parse::<u8>() {
+            Ok(mines) if (1..=9).contains(&mines) => return mines,
+            _ => {
+                println!("Invalid input. Please enter 1..9.");
                 continue;
             }
         }
@@ -146,6 +872,4 @@ fn get_params() -> Result<(usize, usize), &'static str> {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-}
+mod tests {}
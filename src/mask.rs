@@ -0,0 +1,217 @@
+//! Shaped boards.
+//!
+//! A mask marks which cells of the bounding rectangle are part of the board. Cells outside the
+//! mask become void: they cannot be played, never count as a neighbour and are drawn blank.
+//! Shape files are plain text, with `#` for a cell on the board and `.` or a space for a void
+//! cell. Short lines are padded with void cells up to the longest line
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Which cells of a width by height rectangle are part of the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<bool>,
+}
+
+impl Mask {
+    /// Builds a mask by asking whether each (x, y) cell is part of the board
+    pub fn from_fn(width: usize, height: usize, f: impl Fn(usize, usize) -> bool) -> Mask {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(f(x, y));
+            }
+        }
+        Mask {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Every cell is part of the board
+    pub fn rectangle(width: usize, height: usize) -> Mask {
+        Mask::from_fn(width, height, |_, _| true)
+    }
+
+    /// A rectangle with the middle third of each side cut out of its centre
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::mask::Mask;
+    /// let mask = Mask::with_hole(9, 9);
+    ///
+    /// assert!(mask.contains(0, 0));
+    /// assert!(!mask.contains(4, 4));
+    /// assert_eq!(mask.count(), 72);
+    /// ```
+    pub fn with_hole(width: usize, height: usize) -> Mask {
+        Mask::from_fn(width, height, |x, y| {
+            !(middle_third(x, width) && middle_third(y, height))
+        })
+    }
+
+    /// A plus sign whose arms are the middle third of the rectangle
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::mask::Mask;
+    /// let mask = Mask::cross(9, 9);
+    ///
+    /// assert!(!mask.contains(0, 0));
+    /// assert!(mask.contains(4, 0));
+    /// assert!(mask.contains(0, 4));
+    /// ```
+    pub fn cross(width: usize, height: usize) -> Mask {
+        Mask::from_fn(width, height, |x, y| {
+            middle_third(x, width) || middle_third(y, height)
+        })
+    }
+
+    /// The largest ellipse that fits in the rectangle, keeping every cell whose centre is inside
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::mask::Mask;
+    /// let mask = Mask::circle(7, 7);
+    ///
+    /// assert!(!mask.contains(0, 0));
+    /// assert!(mask.contains(3, 3));
+    /// assert!(mask.contains(3, 0));
+    /// ```
+    pub fn circle(width: usize, height: usize) -> Mask {
+        let (radius_x, radius_y) = (width as f64 / 2.0, height as f64 / 2.0);
+        Mask::from_fn(width, height, |x, y| {
+            let dx = (x as f64 + 0.5 - radius_x) / radius_x;
+            let dy = (y as f64 + 0.5 - radius_y) / radius_y;
+            dx * dx + dy * dy <= 1.0
+        })
+    }
+
+    /// Reads a shape from text, with `#` for a cell on the board and `.` or a space for a void
+    /// cell. Errors give the line and column of the problem
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::mask::Mask;
+    /// let mask = Mask::parse(".#.\n###\n.#").unwrap();
+    ///
+    /// assert_eq!((mask.width, mask.height), (3, 3));
+    /// assert_eq!(mask.count(), 5);
+    /// assert!(!mask.contains(2, 2));
+    ///
+    /// assert_eq!(Mask::parse("#x").unwrap_err(), "unexpected 'x' at line 1, column 2");
+    /// ```
+    pub fn parse(text: &str) -> Result<Mask, String> {
+        let lines: Vec<&str> = text.lines().map(|line| line.trim_end()).collect();
+        // blank lines after the shape are ignored, but ones inside it are void rows
+        let height = lines
+            .iter()
+            .rposition(|line| !line.is_empty())
+            .map_or(0, |last| last + 1);
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut cells = vec![false; width * height];
+        for (y, line) in lines.iter().take(height).enumerate() {
+            for (x, symbol) in line.chars().enumerate() {
+                match symbol {
+                    '#' => cells[y * width + x] = true,
+                    '.' | ' ' => {}
+                    _ => {
+                        return Err(format!(
+                            "unexpected '{}' at line {}, column {}",
+                            symbol,
+                            y + 1,
+                            x + 1
+                        ))
+                    }
+                }
+            }
+        }
+        let mask = Mask {
+            width,
+            height,
+            cells,
+        };
+        if mask.count() == 0 {
+            return Err(String::from("the shape has no cells"));
+        }
+        Ok(mask)
+    }
+
+    /// Reads a shape file, see Mask::parse for the format
+    pub fn load(path: &Path) -> io::Result<Mask> {
+        let text = fs::read_to_string(path)?;
+        Mask::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns true if the cell at (x, y) is part of the board
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.cells[y * self.width + x]
+    }
+
+    /// Number of cells that are part of the board
+    pub fn count(&self) -> usize {
+        self.cells.iter().filter(|&&cell| cell).count()
+    }
+}
+
+/// Returns true if the index is in the middle third of the length
+fn middle_third(index: usize, length: usize) -> bool {
+    (length / 3..length - length / 3).contains(&index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Board, MinesweeperSquare, RevealOutcome};
+
+    #[test]
+    fn test_parse_pads_short_lines() {
+        let mask = Mask::parse("#\n##.\n\n#\n\n").unwrap();
+        assert_eq!((mask.width, mask.height), (3, 4));
+        assert!(!mask.contains(1, 0));
+        assert!(!mask.contains(0, 2));
+        assert!(mask.contains(0, 3));
+        assert_eq!(
+            Mask::parse("##\n #\n#*").unwrap_err(),
+            "unexpected '*' at line 3, column 2"
+        );
+        assert!(Mask::parse(" .\n").is_err());
+    }
+
+    #[test]
+    fn test_void_squares_are_not_neighbours() {
+        // # . #
+        // the mine at (2, 0) is only reachable through the void square
+        let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 1);
+        board.apply_mask(&Mask::parse("#.#").unwrap()).unwrap();
+        let mut mine = MinesweeperSquare::new();
+        mine.set_is_mine(true);
+        board.set(2, 0, mine);
+
+        assert!(board.reveal(1, 0).is_err());
+        assert_eq!(board.reveal(0, 0), Ok(RevealOutcome::Revealed(1)));
+        assert_eq!(board.get(0, 0).unwrap().get_value(), 0);
+        assert!(board.is_won().is_some());
+    }
+
+    #[test]
+    fn test_mines_only_land_on_the_mask() {
+        let mask = Mask::cross(9, 9);
+        let mut board: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+        board.apply_mask(&mask).unwrap();
+        board.place_mines(100);
+        assert_eq!(board.mine_count(), mask.count());
+        assert!(board.apply_mask(&mask).is_err());
+        assert!(board
+            .to_string()
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("a           | "));
+    }
+}
@@ -0,0 +1,137 @@
+//! Minesweeper Board Format.
+//!
+//! MBF is the binary layout format shared by most Minesweeper clients. A file is the width and
+//! height as one byte each, the number of mines as two bytes (big endian), then the x and y of
+//! every mine as one byte each, counted from the top left square. It holds only where the mines
+//! are, so it is for full rectangular boards with at most one mine per square.
+use crate::{Board, MinesweeperSquare};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Writes where the board's mines are as MBF
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::{mbf, Board, MinesweeperSquare};
+/// let mut board: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+/// board.place_mines_seeded(10, 1);
+/// let bytes = mbf::write(&board).unwrap();
+///
+/// assert_eq!(&bytes[..4], &[9, 9, 0, 10]);
+/// assert_eq!(bytes.len(), 4 + 2 * 10);
+/// ```
+pub fn write(board: &Board<MinesweeperSquare>) -> Result<Vec<u8>, &'static str> {
+    if board.width > u8::MAX as usize || board.height > u8::MAX as usize {
+        return Err("MBF boards can be at most 255 squares wide and tall");
+    }
+    if board.is_shaped() {
+        return Err("MBF boards must be rectangles");
+    }
+    let mut mines = Vec::new();
+    for (y, row) in board.iter().enumerate() {
+        for (x, square) in row.iter().enumerate() {
+            match square.mines {
+                0 => {}
+                1 => mines.extend([x as u8, y as u8]),
+                _ => return Err("MBF boards can only have one mine per square"),
+            }
+        }
+    }
+    let count = u16::try_from(mines.len() / 2).map_err(|_| "MBF boards can hold 65535 mines")?;
+
+    let mut bytes = vec![board.width as u8, board.height as u8];
+    bytes.extend(count.to_be_bytes());
+    bytes.extend(mines);
+    Ok(bytes)
+}
+
+/// Reads a board of hidden squares from MBF. Errors give the byte the problem was found at
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::mbf;
+/// let board = mbf::read(&[3, 2, 0, 1, 2, 1]).unwrap();
+///
+/// assert_eq!((board.width, board.height), (3, 2));
+/// assert!(board.get(2, 1).unwrap().get_is_mine());
+///
+/// assert_eq!(mbf::read(&[3, 2, 0, 1, 3, 1]).err().unwrap(), "mine is off the board at byte 4");
+/// ```
+pub fn read(bytes: &[u8]) -> Result<Board<MinesweeperSquare>, String> {
+    if bytes.len() < 4 {
+        return Err(format!(
+            "expected a 4 byte header but the file is {} bytes",
+            bytes.len()
+        ));
+    }
+    let (width, height) = (bytes[0] as usize, bytes[1] as usize);
+    if width == 0 || height == 0 {
+        return Err(String::from("the board has no squares at byte 0"));
+    }
+    let count = u16::from_be_bytes([bytes[2], bytes[3]]) as usize;
+    if bytes.len() != 4 + 2 * count {
+        return Err(format!(
+            "expected {} bytes for {} mines but the file is {} bytes",
+            4 + 2 * count,
+            count,
+            bytes.len()
+        ));
+    }
+
+    let mut board: Board<MinesweeperSquare> = Board::isize_board(width, height);
+    for (index, mine) in bytes[4..].chunks(2).enumerate() {
+        let (x, y) = (mine[0] as usize, mine[1] as usize);
+        let offset = 4 + 2 * index;
+        if x >= width || y >= height {
+            return Err(format!("mine is off the board at byte {}", offset));
+        }
+        if board[(x, y)].mines > 0 {
+            return Err(format!("mine is listed twice at byte {}", offset));
+        }
+        board.change_mines(x, y, 1);
+    }
+    Ok(board)
+}
+
+/// Reads an MBF file, see read
+pub fn load(path: &Path) -> io::Result<Board<MinesweeperSquare>> {
+    let bytes = fs::read(path)?;
+    read(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Writes the board to an MBF file
+pub fn save(path: &Path, board: &Board<MinesweeperSquare>) -> io::Result<()> {
+    let bytes = write(board).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    fs::write(path, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut board: Board<MinesweeperSquare> = Board::isize_board(30, 16);
+        board.place_mines_seeded(99, 12);
+        let read = read(&write(&board).unwrap()).unwrap();
+        assert_eq!((read.width, read.height), (30, 16));
+        for y in 0..16 {
+            for x in 0..30 {
+                assert_eq!(read[(x, y)].mines, board[(x, y)].mines);
+            }
+        }
+
+        board[(0, 0)].mines = 2;
+        assert!(write(&board).is_err());
+        assert!(write(&Board::isize_board(256, 1)).is_err());
+    }
+
+    #[test]
+    fn test_rejects_bad_files() {
+        assert!(read(&[9, 9, 0]).is_err());
+        assert!(read(&[9, 9, 0, 2, 1, 1]).is_err());
+        assert_eq!(
+            read(&[9, 9, 0, 2, 1, 1, 1, 1]).err().unwrap(),
+            "mine is listed twice at byte 6"
+        );
+    }
+}
@@ -0,0 +1,375 @@
+//! Puzzles.
+//!
+//! A puzzle starts with some safe squares already revealed as clues and nothing else opened. The
+//! clues are chosen so that every mine can be found without guessing. The checker counts how
+//! many ways the mines could be laid out under what is visible, and the generator starts from a
+//! board with every safe square shown and takes clues away for as long as the puzzle can still
+//! be solved, leaving a puzzle where no clue can be dropped.
+use crate::solver::{self, Deductions};
+use crate::view::PlayerView;
+use crate::{Board, MinesweeperSquare, SquareState};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// Width, height and mines of a puzzle unless another size is chosen
+pub const DEFAULT_SIZE: (usize, usize, usize) = (8, 8, 12);
+/// Most squares a generated puzzle can have, as hard puzzles are checked by counting solutions
+pub const MAX_SQUARES: usize = 100;
+/// Boards tried by generate before giving up on the difficulty
+const MAX_ATTEMPTS: usize = 50;
+
+/// How much reasoning a puzzle needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    /// Every step follows from a single number
+    Easy,
+    /// Needs numbers to be compared in pairs
+    Medium,
+    /// Has one solution, but needs more than comparing pairs of numbers to find it
+    Hard,
+}
+
+impl Difficulty {
+    /// Every difficulty, easiest first
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    /// Returns the lowercase name of the difficulty
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    /// Finds the difficulty with the given name, ignoring case
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Returns the next easier difficulty, if there is one
+    fn easier(self) -> Option<Difficulty> {
+        match self {
+            Difficulty::Easy => None,
+            Difficulty::Medium => Some(Difficulty::Easy),
+            Difficulty::Hard => Some(Difficulty::Medium),
+        }
+    }
+}
+
+/// A generated puzzle: the board with its clues revealed, ready to be played
+pub struct Puzzle {
+    pub board: Board<MinesweeperSquare>,
+    pub difficulty: Difficulty,
+    pub seed: u64,
+}
+
+impl Puzzle {
+    /// Returns the number of clues the puzzle starts with
+    pub fn clues(&self) -> usize {
+        self.board
+            .iter()
+            .flatten()
+            .filter(|square| square.state == SquareState::Revealed)
+            .count()
+    }
+}
+
+/// Number of ways to choose k of n things, stopping at limit
+fn choose(n: usize, k: usize, limit: usize) -> usize {
+    let mut ways: u128 = 1;
+    for i in 0..k.min(n - k) {
+        ways = ways * (n - i) as u128 / (i + 1) as u128;
+        if ways >= limit as u128 {
+            return limit;
+        }
+    }
+    ways as usize
+}
+
+/// A revealed number and the indexes of the unknown squares around it
+struct Clue {
+    squares: Vec<usize>,
+    mines: usize,
+}
+
+/// Counts the ways the mines could be laid out under the squares that are not revealed, given
+/// the revealed numbers and the number of mines on the board. Flags are ignored, as they are
+/// only the player's guess. Counting stops at limit
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::layout;
+/// use rusty_mine_sweeper::puzzle::solution_count;
+///
+/// // the 1 says one of the two squares on the right is a mine, but not which
+/// let board = layout::parse("01.\n01*").unwrap();
+/// assert_eq!(solution_count(&board, 10), 2);
+///
+/// let board = layout::parse("01*").unwrap();
+/// assert_eq!(solution_count(&board, 10), 1);
+/// ```
+pub fn solution_count(board: &Board<MinesweeperSquare>, limit: usize) -> usize {
+    // index of each square that is not revealed, in the order they are listed in unknown
+    let mut index = vec![vec![None; board.width]; board.height];
+    let mut unknown = Vec::new();
+    let mut found_mines = 0;
+    for (y, row) in board.iter().enumerate() {
+        for (x, square) in row.iter().enumerate() {
+            match square.state {
+                SquareState::Hidden | SquareState::Flagged => {
+                    index[y][x] = Some(unknown.len());
+                    unknown.push((x, y));
+                }
+                SquareState::Revealed if square.is_mine() => found_mines += square.mines as usize,
+                SquareState::Revealed | SquareState::Void => {}
+            }
+        }
+    }
+
+    let mut clues = Vec::new();
+    for (y, row) in board.iter().enumerate() {
+        for (x, square) in row.iter().enumerate() {
+            if square.state != SquareState::Revealed || square.is_mine() {
+                continue;
+            }
+            let mut squares = Vec::new();
+            let mut mines = square.value.max(0) as usize;
+            for (x_index, y_index) in board.neighbours(x, y) {
+                match index[y_index][x_index] {
+                    Some(unknown) => squares.push(unknown),
+                    None => mines = mines.saturating_sub(board[(x_index, y_index)].mines as usize),
+                }
+            }
+            if squares.len() < mines {
+                return 0;
+            }
+            clues.push(Clue { squares, mines });
+        }
+    }
+
+    // squares next to a clue are tried one by one, the rest only matter by how many there are
+    let mut frontier = Vec::new();
+    let mut on_frontier = vec![false; unknown.len()];
+    for clue in &clues {
+        for &square in &clue.squares {
+            if !on_frontier[square] {
+                on_frontier[square] = true;
+                frontier.push(square);
+            }
+        }
+    }
+    let mut clues_of = vec![Vec::new(); unknown.len()];
+    for (number, clue) in clues.iter().enumerate() {
+        for &square in &clue.squares {
+            clues_of[square].push(number);
+        }
+    }
+
+    let mut search = Search {
+        clues: &clues,
+        clues_of: &clues_of,
+        frontier: &frontier,
+        placed: vec![0; clues.len()],
+        left: clues.iter().map(|clue| clue.squares.len()).collect(),
+        mines: board.mine_count().saturating_sub(found_mines),
+        others: unknown.len() - frontier.len(),
+        limit,
+    };
+    search.count(0, 0)
+}
+
+/// State of the backtracking search over the squares next to clues
+struct Search<'a> {
+    clues: &'a [Clue],
+    clues_of: &'a [Vec<usize>],
+    frontier: &'a [usize],
+    /// Mines placed so far around each clue
+    placed: Vec<usize>,
+    /// Squares around each clue not yet decided
+    left: Vec<usize>,
+    /// Mines left to place under unknown squares
+    mines: usize,
+    /// Unknown squares that are not next to any clue
+    others: usize,
+    limit: usize,
+}
+
+impl Search<'_> {
+    /// Counts the layouts of the frontier from position on, with used mines already placed
+    fn count(&mut self, position: usize, used: usize) -> usize {
+        if used > self.mines {
+            return 0;
+        }
+        let Some(&square) = self.frontier.get(position) else {
+            return match self.mines - used {
+                rest if rest <= self.others => choose(self.others, rest, self.limit),
+                _ => 0,
+            };
+        };
+
+        let mut total = 0;
+        for mine in [false, true] {
+            let mut fits = true;
+            for &clue in &self.clues_of[square] {
+                self.left[clue] -= 1;
+                self.placed[clue] += usize::from(mine);
+                let needed = self.clues[clue].mines;
+                fits &=
+                    self.placed[clue] <= needed && self.placed[clue] + self.left[clue] >= needed;
+            }
+            if fits {
+                total += self.count(position + 1, used + usize::from(mine));
+            }
+            for &clue in &self.clues_of[square] {
+                self.left[clue] += 1;
+                self.placed[clue] -= usize::from(mine);
+            }
+            if total >= self.limit {
+                return self.limit;
+            }
+        }
+        total
+    }
+}
+
+/// Returns true if the puzzle has exactly one solution
+pub fn has_unique_solution(board: &Board<MinesweeperSquare>) -> bool {
+    solution_count(board, 2) == 1
+}
+
+/// Returns true if the puzzle has one solution that can be found at the difficulty. Easy and
+/// medium puzzles are also played out with the solver's rules for their difficulty, revealing
+/// and flagging as it goes, to check that those rules are enough
+pub fn is_solvable(original: &Board<MinesweeperSquare>, difficulty: Difficulty) -> bool {
+    let deduce: fn(&PlayerView) -> Deductions = match difficulty {
+        Difficulty::Easy => solver::deduce_simple,
+        Difficulty::Medium => solver::deduce,
+        Difficulty::Hard => return has_unique_solution(original),
+    };
+    let mut board = original.clone();
+    // squares flagged by the player are not known to be mines
+    for square in board.board.iter_mut() {
+        if square.state == SquareState::Flagged {
+            square.state = SquareState::Hidden;
+            square.flags = 0;
+        }
+    }
+    while !board.is_cleared() {
+        let deductions = deduce(&PlayerView::new(&board));
+        if deductions.is_empty() {
+            return false;
+        }
+        for (x, y) in deductions.safe {
+            let _ = board.reveal(x, y);
+        }
+        for (x, y) in deductions.mines {
+            let _ = board.toggle_flag(x, y);
+        }
+    }
+    has_unique_solution(original)
+}
+
+/// Makes a puzzle with the given size and number of mines that can be solved at the difficulty
+/// but not at any easier one, with as few clues as that allows
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::puzzle::{generate, is_solvable, Difficulty};
+/// let puzzle = generate(6, 6, 8, Difficulty::Easy, 3).unwrap();
+///
+/// assert!(is_solvable(&puzzle.board, Difficulty::Easy));
+/// assert!(puzzle.clues() < 6 * 6 - 8);
+/// ```
+pub fn generate(
+    width: usize,
+    height: usize,
+    mines: usize,
+    difficulty: Difficulty,
+    seed: u64,
+) -> Result<Puzzle, &'static str> {
+    if width * height > MAX_SQUARES {
+        return Err("Puzzles can have at most 100 squares");
+    }
+    if mines == 0 || mines >= width * height {
+        return Err("Puzzles need at least one mine and one safe square");
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    for _ in 0..MAX_ATTEMPTS {
+        let mut board: Board<MinesweeperSquare> = Board::isize_board(width, height);
+        board.place_mines_with_rng(mines, &mut rng);
+        let mut clues = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                if !board[(x, y)].is_mine() {
+                    board[(x, y)].value = board.check_square(x, y);
+                    board[(x, y)].state = SquareState::Revealed;
+                    clues.push((x, y));
+                }
+            }
+        }
+
+        clues.shuffle(&mut rng);
+        for (x, y) in clues {
+            board[(x, y)].state = SquareState::Hidden;
+            if !is_solvable(&board, difficulty) {
+                board[(x, y)].state = SquareState::Revealed;
+            }
+        }
+        // hidden squares keep their value, so put it back to how a new board has it
+        for square in board.board.iter_mut() {
+            if square.state == SquareState::Hidden {
+                square.value = -1;
+            }
+        }
+        let too_easy = difficulty
+            .easier()
+            .is_some_and(|easier| is_solvable(&board, easier));
+        if !too_easy {
+            return Ok(Puzzle {
+                board,
+                difficulty,
+                seed,
+            });
+        }
+    }
+    Err("Could not make a puzzle of that difficulty, try another seed or size")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout;
+
+    #[test]
+    fn test_counts_unconstrained_squares() {
+        // the 0 clears three squares, leaving two mines among the other 11, and the flag is
+        // only a guess
+        let board = layout::parse("0f...\n.....\n*...*").unwrap();
+        assert_eq!(solution_count(&board, 1000), 55);
+        assert_eq!(solution_count(&board, 10), 10);
+        assert!(!has_unique_solution(&board));
+    }
+
+    #[test]
+    fn test_generated_puzzles_are_minimal() {
+        for difficulty in [Difficulty::Medium, Difficulty::Hard] {
+            let puzzle = generate(7, 7, 10, difficulty, 11).unwrap();
+            assert!(has_unique_solution(&puzzle.board));
+            assert!(is_solvable(&puzzle.board, difficulty));
+            assert!(!is_solvable(&puzzle.board, difficulty.easier().unwrap()));
+
+            let mut board = puzzle.board.clone();
+            for y in 0..7 {
+                for x in 0..7 {
+                    if board[(x, y)].state == SquareState::Revealed {
+                        board[(x, y)].state = SquareState::Hidden;
+                        assert!(!is_solvable(&board, difficulty));
+                        board[(x, y)].state = SquareState::Revealed;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::tcp::Client;
+use crate::{Board, MinesweeperSquare, Preset, SquareState};
+use std::io::{self, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// How a player's game came to an end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finish {
+    /// Every safe square was revealed
+    Cleared,
+    /// A mine was revealed
+    HitMine,
+    /// The player disconnected or was idle for too long
+    Left,
+}
+
+/// Percentage of the safe squares on the board that have been revealed
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::race::progress;
+/// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+/// let mut board: Board<MinesweeperSquare> = Board::isize_board(2, 2);
+///
+/// assert_eq!(progress(&board), 0);
+/// board.reveal(0, 0).unwrap();
+/// assert_eq!(progress(&board), 100);
+/// ```
+pub fn progress(board: &Board<MinesweeperSquare>) -> usize {
+    let safe = board
+        .iter()
+        .flatten()
+        .filter(|square| !square.is_mine() && square.state != SquareState::Void);
+    let (total, revealed) = safe.fold((0, 0), |(total, revealed), square| {
+        (
+            total + 1,
+            revealed + usize::from(square.state == SquareState::Revealed),
+        )
+    });
+    (revealed * 100).checked_div(total).unwrap_or(100)
+}
+
+/// The shared state of a race between two connected players
+struct Race {
+    streams: [TcpStream; 2],
+    state: Mutex<RaceState>,
+}
+
+#[derive(Default)]
+struct RaceState {
+    progress: [usize; 2],
+    winner: Option<usize>,
+}
+
+impl Race {
+    fn lock(&self) -> std::sync::MutexGuard<'_, RaceState> {
+        match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Sends the text to the player, ignoring players that have already hung up
+    fn send(&self, player: usize, text: &str) {
+        let _ = (&self.streams[player]).write_all(text.as_bytes());
+    }
+
+    /// Records the player's progress and tells their opponent when it changes
+    fn update(&self, player: usize, board: &Board<MinesweeperSquare>) {
+        let percent = progress(board);
+        let mut state = self.lock();
+        if state.winner.is_some() || state.progress[player] == percent {
+            return;
+        }
+        state.progress[player] = percent;
+        self.send(
+            1 - player,
+            &format!("\n[Your opponent has cleared {}% of the board]\n", percent),
+        );
+    }
+
+    /// Ends the race the first time a player finishes, telling both players the result and
+    /// hanging up on them. Later calls do nothing. Returns the winner
+    fn finish(&self, player: usize, finish: Finish) -> usize {
+        let mut state = self.lock();
+        if let Some(winner) = state.winner {
+            return winner;
+        }
+        let winner = match finish {
+            Finish::Cleared => player,
+            Finish::HitMine | Finish::Left => 1 - player,
+        };
+        state.winner = Some(winner);
+        let reason = match finish {
+            Finish::Cleared => "cleared the board first",
+            Finish::HitMine => "hit a mine",
+            Finish::Left => "left the race",
+        };
+        for (index, stream) in self.streams.iter().enumerate() {
+            let who = if index == player {
+                "You"
+            } else {
+                "Your opponent"
+            };
+            let result = if index == winner {
+                "You win the race!"
+            } else {
+                "You lose the race."
+            };
+            self.send(
+                index,
+                &format!(
+                    "\n{} {}. {} Progress: you {}%, opponent {}%\n",
+                    who,
+                    reason,
+                    result,
+                    state.progress[index],
+                    state.progress[1 - index]
+                ),
+            );
+            // wakes up the other player's thread, which is waiting on their next answer
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+        winner
+    }
+}
+
+/// Plays one side of the race on its own board
+fn play(race: &Race, player: usize, board: Board<MinesweeperSquare>) -> usize {
+    let stream = &race.streams[player];
+    let result = stream.try_clone().and_then(|reader| {
+        let mut client = Client::new(BufReader::new(reader), stream);
+        client.play_game_watched(board, |board| race.update(player, board))
+    });
+    let finish = match result {
+        Ok(report) if report.won => Finish::Cleared,
+        Ok(_) => Finish::HitMine,
+        Err(_) => Finish::Left,
+    };
+    race.finish(player, finish)
+}
+
+/// Waits for two players to connect to the listener, then races them on identical boards
+/// generated from the preset and seed. Returns the index of the winner, 0 for the player that
+/// connected first
+pub fn host(
+    listener: &TcpListener,
+    preset: Preset,
+    seed: u64,
+    idle_timeout: Duration,
+) -> io::Result<usize> {
+    let (first, _) = listener.accept()?;
+    first.set_read_timeout(Some(idle_timeout))?;
+    (&first).write_all(b"Waiting for an opponent to join...\n")?;
+    let (second, _) = listener.accept()?;
+    second.set_read_timeout(Some(idle_timeout))?;
+
+    let race = Arc::new(Race {
+        streams: [first, second],
+        state: Mutex::new(RaceState::default()),
+    });
+    for player in 0..2 {
+        race.send(
+            player,
+            &format!(
+                "Race on the {} board with seed {}. Clear it before your opponent, a mine loses the race. \
+                 You are player {}\n",
+                preset.name(),
+                seed,
+                player + 1
+            ),
+        );
+    }
+    let players: Vec<_> = (0..2)
+        .map(|player| {
+            let race = Arc::clone(&race);
+            thread::spawn(move || play(&race, player, preset.board_with_seed(seed)))
+        })
+        .collect();
+    let mut winner = 0;
+    for player in players {
+        winner = player
+            .join()
+            .map_err(|_| io::Error::other("a player's game panicked"))?;
+    }
+    Ok(winner)
+}
+
+/// Connects to a race hosted at the address, passing the terminal through to it until the race
+/// is over
+pub fn join(address: &str) -> io::Result<()> {
+    let stream = TcpStream::connect(address)?;
+    let mut writer = stream.try_clone()?;
+    thread::spawn(move || io::copy(&mut io::stdin().lock(), &mut writer));
+    io::copy(&mut &stream, &mut io::stdout().lock())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_ignores_mines() {
+        let mut board: Board<MinesweeperSquare> = Board::isize_board(4, 1);
+        let mut mine = MinesweeperSquare::new();
+        mine.set_is_mine(true);
+        board.set(3, 0, mine);
+        board.reveal(2, 0).unwrap();
+        assert_eq!(progress(&board), 33);
+    }
+}
@@ -0,0 +1,457 @@
+//! RAW video format replays.
+//!
+//! RAWVF is the text replay format used by the Minesweeper community. A replay starts with
+//! `Key: Value` header lines, including the Width, Height and Mines of the board, then `Board:`
+//! followed by one line per row with `*` for a mine and `0` for a safe square, then `Events:`
+//! followed by one line per mouse event:
+//!
+//! ```text
+//! 0.52 lr 3 5 (40 72)
+//! ```
+//!
+//! That is the time in seconds, the event, the column and row of the square counting from 1 and
+//! the mouse position in pixels. Only the events that change the board are played back: a left
+//! release (lr) reveals a square, a right click (rc) flags one and a middle release (mr) chords.
+//! Mouse moves and the other events are kept but do nothing.
+use crate::{Board, MinesweeperSquare};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Size of a square in pixels, used for the mouse positions of recorded events
+const SQUARE_PIXELS: usize = 16;
+/// Largest width or height of a replay's board, the same limit MBF boards have
+const MAX_SIZE: usize = 255;
+
+/// A move that changes the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Reveal,
+    Flag,
+    Chord,
+}
+
+impl Action {
+    /// The RAWVF event that makes the move
+    fn event(self) -> &'static str {
+        match self {
+            Action::Reveal => "lr",
+            Action::Flag => "rc",
+            Action::Chord => "mr",
+        }
+    }
+}
+
+/// One line of the events of a replay
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    /// Seconds since the start of the game
+    pub time: f64,
+    /// The RAWVF name of the event, such as lr or mv
+    pub kind: String,
+    /// The (x, y) cords of the square under the mouse, if the event has one
+    pub square: Option<(usize, usize)>,
+}
+
+impl Event {
+    /// Returns the move the event makes, if it changes the board
+    pub fn action(&self) -> Option<Action> {
+        match self.kind.as_str() {
+            "lr" => Some(Action::Reveal),
+            "rc" => Some(Action::Flag),
+            "mr" => Some(Action::Chord),
+            _ => None,
+        }
+    }
+}
+
+/// A recorded game: the board it was played on and everything the player did
+pub struct Replay {
+    /// Header fields other than the board size and mine count, in file order
+    pub header: Vec<(String, String)>,
+    /// The board before any move was made
+    pub board: Board<MinesweeperSquare>,
+    pub events: Vec<Event>,
+}
+
+impl Replay {
+    /// Starts a recording of a game on the board, which keeps only where its mines are
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::rawvf::{Action, Replay};
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(3, 1);
+    /// let mut mine = MinesweeperSquare::new();
+    /// mine.set_is_mine(true);
+    /// board.set(2, 0, mine);
+    /// let mut replay = Replay::new(&board);
+    /// replay.record(0.0, Action::Reveal, 0, 0);
+    /// replay.record(1.25, Action::Flag, 2, 0);
+    ///
+    /// let text = replay.to_string();
+    /// assert!(text.contains("Board:\n00*\n"));
+    /// assert!(text.ends_with("Events:\n0.00 lr 1 1 (8 8)\n1.25 rc 3 1 (40 8)\n"));
+    /// ```
+    pub fn new(board: &Board<MinesweeperSquare>) -> Replay {
+        let mut start: Board<MinesweeperSquare> = Board::isize_board(board.width, board.height);
+        for (y, row) in board.iter().enumerate() {
+            for (x, square) in row.iter().enumerate() {
+                start.change_mines(x, y, square.mines);
+            }
+        }
+        Replay {
+            header: vec![
+                (String::from("Program"), String::from("rusty-mine-sweeper")),
+                (String::from("Marks"), String::from("Off")),
+            ],
+            board: start,
+            events: Vec::new(),
+        }
+    }
+
+    /// Adds a move on the square at the (x, y) cords to the end of the replay
+    pub fn record(&mut self, time: f64, action: Action, x: usize, y: usize) {
+        self.events.push(Event {
+            time,
+            kind: action.event().to_string(),
+            square: Some((x, y)),
+        });
+    }
+
+    /// Reads a replay. Errors give the line of the problem
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::rawvf::{Action, Replay};
+    /// let text = "RawVF_Version: Rev5\nWidth: 2\nHeight: 1\nMines: 1\nBoard:\n0*\n\
+    ///             Events:\n0.00 mv 1 1 (5 5)\n0.10 lr 1 1 (8 8)\n";
+    /// let replay = Replay::parse(text).unwrap();
+    ///
+    /// assert_eq!(replay.board.mine_count(), 1);
+    /// assert_eq!(replay.events.len(), 2);
+    /// assert_eq!(replay.events[1].action(), Some(Action::Reveal));
+    /// assert_eq!(replay.events[1].square, Some((0, 0)));
+    /// ```
+    pub fn parse(text: &str) -> Result<Replay, String> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()));
+        let mut header = Vec::new();
+        let (mut width, mut height, mut mines) = (None, None, None);
+        let mut board_line = 0;
+        for (number, line) in lines.by_ref() {
+            if line == "Board:" {
+                board_line = number;
+                break;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("expected Key: Value at line {}", number))?;
+            let value = value.trim();
+            let size = || {
+                value
+                    .parse::<usize>()
+                    .map_err(|_| format!("invalid {} at line {}", key, number))
+            };
+            // checked before the board is allocated, so a huge header cannot exhaust memory
+            let side = || {
+                size().and_then(|side| match side {
+                    1..=MAX_SIZE => Ok(side),
+                    _ => Err(format!(
+                        "{} must be from 1 to {} at line {}",
+                        key, MAX_SIZE, number
+                    )),
+                })
+            };
+            match key {
+                "Width" => width = Some(side()?),
+                "Height" => height = Some(side()?),
+                "Mines" => mines = Some(size()?),
+                "RawVF_Version" => {}
+                _ => header.push((key.to_string(), value.to_string())),
+            }
+        }
+        let (width, height, mines) = match (width, height, mines) {
+            _ if board_line == 0 => return Err(String::from("the replay has no Board: section")),
+            (Some(width), Some(height), Some(mines))
+                if width
+                    .checked_mul(height)
+                    .is_some_and(|squares| mines <= squares) =>
+            {
+                (width, height, mines)
+            }
+            (Some(_), Some(_), Some(mines)) => {
+                return Err(format!(
+                    "the header has {} mines, more than the board has squares, before line {}",
+                    mines, board_line
+                ))
+            }
+            _ => {
+                return Err(format!(
+                    "the header needs a Width, Height and Mines before line {}",
+                    board_line
+                ))
+            }
+        };
+
+        let mut board: Board<MinesweeperSquare> = Board::isize_board(width, height);
+        for y in 0..height {
+            let (number, line) = lines
+                .next()
+                .ok_or_else(|| format!("the board should have {} rows", height))?;
+            if line.chars().count() != width {
+                return Err(format!(
+                    "expected {} squares in the row at line {}",
+                    width, number
+                ));
+            }
+            for (x, symbol) in line.chars().enumerate() {
+                match symbol {
+                    '*' => board.change_mines(x, y, 1),
+                    '0' | '.' => {}
+                    _ => {
+                        return Err(format!(
+                            "unexpected '{}' at line {}, column {}",
+                            symbol,
+                            number,
+                            x + 1
+                        ))
+                    }
+                }
+            }
+        }
+        if board.mine_count() != mines {
+            return Err(format!(
+                "the header says {} mines but the board has {}",
+                mines,
+                board.mine_count()
+            ));
+        }
+
+        let mut events = Vec::new();
+        let mut in_events = false;
+        for (number, line) in lines {
+            if line.is_empty() {
+                continue;
+            }
+            if !in_events {
+                if line != "Events:" {
+                    return Err(format!("expected Events: at line {}", number));
+                }
+                in_events = true;
+                continue;
+            }
+            events.push(
+                parse_event(line, width, height)
+                    .map_err(|e| format!("{} at line {}", e, number))?,
+            );
+        }
+        Ok(Replay {
+            header,
+            board,
+            events,
+        })
+    }
+
+    /// Reads a replay file, see parse
+    pub fn load(path: &Path) -> io::Result<Replay> {
+        let text = fs::read_to_string(path)?;
+        Replay::parse(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes the replay to a file
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Starts stepping through the replay from the first move
+    pub fn playback(&self) -> Playback<'_> {
+        Playback {
+            replay: self,
+            board: self.board.clone(),
+            next: 0,
+        }
+    }
+}
+
+/// Reads one event line: the time, the event and, for most events, the column and row
+fn parse_event(line: &str, width: usize, height: usize) -> Result<Event, String> {
+    let mut parts = line.split_whitespace();
+    let time = parts
+        .next()
+        .and_then(|time| time.parse::<f64>().ok())
+        .ok_or("expected the time of the event")?;
+    let kind = parts
+        .next()
+        .ok_or("expected the kind of event")?
+        .to_string();
+    // events without a square end the line here, anything else has to be a column and row
+    let square = match (parts.next(), parts.next()) {
+        (None, _) => None,
+        (Some(column), row) => {
+            let (Ok(column), Some(Ok(row))) =
+                (column.parse::<usize>(), row.map(|row| row.parse::<usize>()))
+            else {
+                return Err(String::from("expected the column and row of the square"));
+            };
+            if column == 0 || row == 0 || column > width || row > height {
+                return Err(String::from("square is off the board"));
+            }
+            Some((column - 1, row - 1))
+        }
+    };
+    Ok(Event { time, kind, square })
+}
+
+impl fmt::Display for Replay {
+    /// Writes the replay as RAWVF
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "RawVF_Version: Rev5")?;
+        for (key, value) in &self.header {
+            writeln!(f, "{}: {}", key, value)?;
+        }
+        writeln!(f, "Width: {}", self.board.width)?;
+        writeln!(f, "Height: {}", self.board.height)?;
+        writeln!(f, "Mines: {}", self.board.mine_count())?;
+        writeln!(f, "Board:")?;
+        for row in self.board.iter() {
+            for square in row {
+                write!(f, "{}", if square.is_mine() { '*' } else { '0' })?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "Events:")?;
+        for event in &self.events {
+            write!(f, "{:.2} {}", event.time, event.kind)?;
+            if let Some((x, y)) = event.square {
+                write!(
+                    f,
+                    " {} {} ({} {})",
+                    x + 1,
+                    y + 1,
+                    x * SQUARE_PIXELS + SQUARE_PIXELS / 2,
+                    y * SQUARE_PIXELS + SQUARE_PIXELS / 2
+                )?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Steps through a replay one move at a time, keeping the board as it was after the last move
+pub struct Playback<'a> {
+    replay: &'a Replay,
+    board: Board<MinesweeperSquare>,
+    next: usize,
+}
+
+impl<'a> Playback<'a> {
+    /// The board after the moves played so far
+    pub fn board(&self) -> &Board<MinesweeperSquare> {
+        &self.board
+    }
+
+    /// Plays the next event that changes the board and returns it, or None at the end of the
+    /// replay. Moves the engine turns down, such as revealing a flagged square, leave the board
+    /// as it was, just as they did in the recorded game
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::rawvf::{Action, Replay};
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// let board: Board<MinesweeperSquare> = Board::isize_board(3, 3);
+    /// let mut replay = Replay::new(&board);
+    /// replay.record(0.0, Action::Reveal, 1, 1);
+    /// let mut playback = replay.playback();
+    ///
+    /// assert_eq!(playback.step().unwrap().square, Some((1, 1)));
+    /// assert!(playback.board().is_won().is_some());
+    /// assert!(playback.step().is_none());
+    /// ```
+    pub fn step(&mut self) -> Option<&'a Event> {
+        while let Some(event) = self.replay.events.get(self.next) {
+            self.next += 1;
+            if let (Some(action), Some((x, y))) = (event.action(), event.square) {
+                let _ = match action {
+                    Action::Reveal => self.board.reveal(x, y).map(|_| ()),
+                    Action::Flag => self.board.toggle_flag(x, y).map(|_| ()),
+                    Action::Chord => self.board.chord(x, y).map(|_| ()),
+                };
+                return Some(event);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_playback() {
+        let mut board: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+        board.place_mines_seeded(10, 3);
+        let mut replay = Replay::new(&board);
+        let safe = (0..81)
+            .map(|index| (index % 9, index / 9))
+            .find(|&(x, y)| !board[(x, y)].is_mine())
+            .unwrap();
+        replay.record(0.5, Action::Reveal, safe.0, safe.1);
+        replay.record(0.75, Action::Flag, safe.0, safe.1);
+
+        let read = Replay::parse(&replay.to_string()).unwrap();
+        assert_eq!(read.events, replay.events);
+        assert_eq!(read.header, replay.header);
+        assert_eq!(read.to_string(), replay.to_string());
+
+        let mut playback = read.playback();
+        playback.step();
+        let revealed = playback.board().to_string();
+        board.reveal(safe.0, safe.1).unwrap();
+        assert_eq!(revealed, board.to_string());
+        // flagging a revealed square is turned down and leaves the board alone
+        playback.step();
+        assert_eq!(playback.board().to_string(), revealed);
+    }
+
+    #[test]
+    fn test_errors_name_the_line() {
+        let header = "Width: 2\nHeight: 1\nMines: 1\nBoard:\n";
+        assert_eq!(
+            Replay::parse(&format!("{}0x\n", header)).err().unwrap(),
+            "unexpected 'x' at line 5, column 2"
+        );
+        assert_eq!(
+            Replay::parse(&format!("{}0*\nEvents:\n0.1 lr 3 1\n", header))
+                .err()
+                .unwrap(),
+            "square is off the board at line 7"
+        );
+        for click in ["0.1 lr x 1", "0.1 lr 1 -1", "0.1 lr 1"] {
+            assert_eq!(
+                Replay::parse(&format!("{}0*\nEvents:\n{}\n", header, click))
+                    .err()
+                    .unwrap(),
+                "expected the column and row of the square at line 7"
+            );
+        }
+        assert!(Replay::parse("Width: 2\nBoard:\n0*\n").is_err());
+        assert_eq!(
+            Replay::parse("Width: 300000\nHeight: 300000\nMines: 1\nBoard:\n")
+                .err()
+                .unwrap(),
+            "Width must be from 1 to 255 at line 1"
+        );
+        assert_eq!(
+            Replay::parse("Width: 2\nHeight: 0\nMines: 1\nBoard:\n")
+                .err()
+                .unwrap(),
+            "Height must be from 1 to 255 at line 2"
+        );
+    }
+}
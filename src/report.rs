@@ -0,0 +1,212 @@
+use crate::{Board, MinesweeperSquare};
+use std::fmt;
+use std::time::Duration;
+
+/// Mine density of the beginner board (10 mines on 9x9). Used as the baseline when weighting the score
+const BASE_DENSITY: f64 = 10.0 / 81.0;
+/// Number of squares on the beginner board. Used as the baseline when weighting the score
+const BASE_SQUARES: f64 = 81.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Counts of each kind of click the player made during a game
+pub struct ClickCounts {
+    pub reveals: usize,
+    pub flags: usize,
+    pub chords: usize,
+}
+
+impl ClickCounts {
+    /// Total number of clicks made
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::report::ClickCounts;
+    /// let clicks = ClickCounts { reveals: 3, flags: 2, chords: 1 };
+    ///
+    /// assert_eq!(clicks.total(), 6);
+    /// ```
+    pub fn total(&self) -> usize {
+        self.reveals + self.flags + self.chords
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// End of game report. Holds everything needed to judge how efficiently a game was played
+pub struct GameReport {
+    pub won: bool,
+    pub elapsed: Duration,
+    pub clicks: ClickCounts,
+    pub three_bv: usize,
+    pub three_bv_solved: usize,
+    pub width: usize,
+    pub height: usize,
+    pub mines: usize,
+}
+
+impl GameReport {
+    /// Creates a report for the board as it stands at the end of the game
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+    /// use rusty_mine_sweeper::report::{ClickCounts, GameReport};
+    /// use std::time::Duration;
+    ///
+    /// let mut board: Board<MinesweeperSquare> = Board::isize_board(4, 4);
+    /// board.reveal(0, 0).unwrap();
+    /// let clicks = ClickCounts { reveals: 1, flags: 0, chords: 0 };
+    /// let report = GameReport::new(&board, clicks, Duration::from_secs(2));
+    ///
+    /// assert!(report.won);
+    /// assert_eq!(report.three_bv, 1);
+    /// assert_eq!(report.efficiency(), 1.0);
+    /// ```
+    pub fn new(
+        board: &Board<MinesweeperSquare>,
+        clicks: ClickCounts,
+        elapsed: Duration,
+    ) -> GameReport {
+        GameReport {
+            won: board.is_won().is_some(),
+            elapsed,
+            clicks,
+            three_bv: board.three_bv(),
+            three_bv_solved: board.three_bv_solved(),
+            width: board.width,
+            height: board.height,
+            mines: board.mine_count(),
+        }
+    }
+
+    /// 3BV cleared per second of play
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::report::{ClickCounts, GameReport};
+    /// use std::time::Duration;
+    /// let report = GameReport {
+    ///     won: true,
+    ///     elapsed: Duration::from_secs(10),
+    ///     clicks: ClickCounts { reveals: 20, flags: 0, chords: 0 },
+    ///     three_bv: 20,
+    ///     three_bv_solved: 20,
+    ///     width: 9,
+    ///     height: 9,
+    ///     mines: 10,
+    /// };
+    ///
+    /// assert_eq!(report.three_bv_per_second(), 2.0);
+    /// ```
+    pub fn three_bv_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds <= 0.0 {
+            return 0.0;
+        }
+        self.three_bv_solved as f64 / seconds
+    }
+
+    /// 3BV cleared divided by the number of clicks made. 1.0 means no click was wasted
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::report::{ClickCounts, GameReport};
+    /// use std::time::Duration;
+    /// let report = GameReport {
+    ///     won: true,
+    ///     elapsed: Duration::from_secs(10),
+    ///     clicks: ClickCounts { reveals: 20, flags: 10, chords: 10 },
+    ///     three_bv: 20,
+    ///     three_bv_solved: 20,
+    ///     width: 9,
+    ///     height: 9,
+    ///     mines: 10,
+    /// };
+    ///
+    /// assert_eq!(report.efficiency(), 0.5);
+    /// ```
+    pub fn efficiency(&self) -> f64 {
+        match self.clicks.total() {
+            0 => 0.0,
+            clicks => self.three_bv_solved as f64 / clicks as f64,
+        }
+    }
+
+    /// Composite score for the game. 3BV/s multiplied by efficiency, then weighted by the size of
+    /// the board and its mine density relative to a beginner board, so larger and denser boards are worth more
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::report::{ClickCounts, GameReport};
+    /// use std::time::Duration;
+    /// let beginner = GameReport {
+    ///     won: true,
+    ///     elapsed: Duration::from_secs(10),
+    ///     clicks: ClickCounts { reveals: 20, flags: 0, chords: 0 },
+    ///     three_bv: 20,
+    ///     three_bv_solved: 20,
+    ///     width: 9,
+    ///     height: 9,
+    ///     mines: 10,
+    /// };
+    /// let expert = GameReport { width: 30, height: 16, mines: 99, ..beginner.clone() };
+    ///
+    /// assert_eq!(beginner.score(), 2000);
+    /// assert!(expert.score() > beginner.score());
+    /// ```
+    pub fn score(&self) -> u64 {
+        let squares = (self.width * self.height) as f64;
+        if squares == 0.0 {
+            return 0;
+        }
+        let size_weight = (squares / BASE_SQUARES).sqrt();
+        let density_weight = (self.mines as f64 / squares) / BASE_DENSITY;
+        (1000.0 * self.three_bv_per_second() * self.efficiency() * size_weight * density_weight)
+            .round() as u64
+    }
+}
+
+/// Displays the report as a small table to be printed at the end of a game
+impl fmt::Display for GameReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Result:     {}", if self.won { "won" } else { "lost" })?;
+        writeln!(
+            f,
+            "Board:      {}x{} with {} mines",
+            self.width, self.height, self.mines
+        )?;
+        writeln!(f, "Time:       {:.2}s", self.elapsed.as_secs_f64())?;
+        writeln!(
+            f,
+            "Clicks:     {} ({} reveals, {} flags, {} chords)",
+            self.clicks.total(),
+            self.clicks.reveals,
+            self.clicks.flags,
+            self.clicks.chords
+        )?;
+        writeln!(f, "3BV:        {}/{}", self.three_bv_solved, self.three_bv)?;
+        writeln!(f, "3BV/s:      {:.2}", self.three_bv_per_second())?;
+        writeln!(f, "Efficiency: {:.0}%", self.efficiency() * 100.0)?;
+        write!(f, "Score:      {}", self.score())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lost_game_only_counts_solved_three_bv() {
+        let mut board: Board<MinesweeperSquare> = Board::isize_board(5, 1);
+        let mut mine = MinesweeperSquare::new();
+        mine.set_is_mine(true);
+        board.set(2, 0, mine);
+        board.reveal(0, 0).unwrap();
+        board.reveal(2, 0).unwrap();
+
+        let clicks = ClickCounts {
+            reveals: 2,
+            flags: 0,
+            chords: 0,
+        };
+        let report = GameReport::new(&board, clicks, Duration::from_secs(1));
+        assert!(!report.won);
+        assert_eq!(report.three_bv, 2);
+        assert_eq!(report.three_bv_solved, 1);
+        assert_eq!(report.efficiency(), 0.5);
+    }
+}
@@ -0,0 +1,303 @@
+use crate::date::Date;
+use crate::storage;
+use crate::Preset;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Name of the high score file inside the data directory
+const SCORES_FILE: &str = "scores.tsv";
+/// Number of entries kept in each table
+pub const TABLE_SIZE: usize = 10;
+/// Longest name that will be stored for a player
+pub const MAX_NAME_LENGTH: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+/// A single entry in a high score table
+pub struct ScoreEntry {
+    pub name: String,
+    pub time: Duration,
+    pub date: Date,
+    pub seed: u64,
+    pub three_bv_per_second: f64,
+}
+
+impl ScoreEntry {
+    /// Creates a new entry. The name is trimmed, stripped of tabs and new lines and cut down to
+    /// MAX_NAME_LENGTH characters. An empty name is stored as "anonymous"
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::date::Date;
+    /// use rusty_mine_sweeper::scores::ScoreEntry;
+    /// use std::time::Duration;
+    /// let date = Date { year: 2024, month: 5, day: 1 };
+    /// let entry = ScoreEntry::new(" Nathan\t", Duration::from_secs(30), date, 1, 1.5);
+    ///
+    /// assert_eq!(entry.name, "Nathan");
+    /// ```
+    pub fn new(
+        name: &str,
+        time: Duration,
+        date: Date,
+        seed: u64,
+        three_bv_per_second: f64,
+    ) -> ScoreEntry {
+        let name: String = name
+            .chars()
+            .filter(|c| !c.is_control())
+            .collect::<String>()
+            .trim()
+            .chars()
+            .take(MAX_NAME_LENGTH)
+            .collect();
+        ScoreEntry {
+            name: if name.is_empty() {
+                "anonymous".to_string()
+            } else {
+                name
+            },
+            time,
+            date,
+            seed,
+            three_bv_per_second,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// The top TABLE_SIZE fastest wins for each preset, saved between runs
+pub struct HighScores {
+    tables: BTreeMap<String, Vec<ScoreEntry>>,
+}
+
+impl HighScores {
+    /// Path of the high score file in the data directory
+    pub fn default_path() -> PathBuf {
+        storage::data_dir().join(SCORES_FILE)
+    }
+
+    /// Loads the high scores from the given file. A missing file gives empty tables
+    pub fn load_from(path: &Path) -> io::Result<HighScores> {
+        HighScores::parse(&storage::read_or_empty(path)?)
+    }
+
+    /// Saves the high scores to the given file, replacing it
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        storage::write_atomic(path, &self.serialize())
+    }
+
+    /// Loads the high scores, applies the change and saves them again while holding a lock on
+    /// the file, so runs finishing at the same time can not overwrite each other's scores
+    pub fn update<T>(path: &Path, change: impl FnOnce(&mut HighScores) -> T) -> io::Result<T> {
+        storage::with_lock(path, || {
+            let mut scores = HighScores::load_from(path)?;
+            let result = change(&mut scores);
+            scores.save_to(path)?;
+            Ok(result)
+        })
+    }
+
+    /// The table for a preset from fastest to slowest
+    pub fn table(&self, preset: Preset) -> &[ScoreEntry] {
+        self.tables
+            .get(preset.name())
+            .map_or(&[], |table| table.as_slice())
+    }
+
+    /// Returns true if a win in the given time would make it into the preset's table
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::scores::HighScores;
+    /// use rusty_mine_sweeper::Preset;
+    /// use std::time::Duration;
+    /// let scores = HighScores::default();
+    ///
+    /// assert!(scores.qualifies(Preset::Beginner, Duration::from_secs(999)));
+    /// ```
+    pub fn qualifies(&self, preset: Preset, time: Duration) -> bool {
+        let table = self.table(preset);
+        table.len() < TABLE_SIZE || table.last().is_some_and(|slowest| time < slowest.time)
+    }
+
+    /// Adds the entry to the preset's table, dropping the slowest entry if the table is full.
+    /// Returns the 1 based rank of the entry, or None if it was not fast enough
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::date::Date;
+    /// use rusty_mine_sweeper::scores::{HighScores, ScoreEntry};
+    /// use rusty_mine_sweeper::Preset;
+    /// use std::time::Duration;
+    /// let date = Date { year: 2024, month: 5, day: 1 };
+    /// let mut scores = HighScores::default();
+    ///
+    /// let slow = ScoreEntry::new("slow", Duration::from_secs(60), date, 1, 0.5);
+    /// let fast = ScoreEntry::new("fast", Duration::from_secs(20), date, 2, 1.5);
+    /// assert_eq!(scores.insert(Preset::Beginner, slow), Some(1));
+    /// assert_eq!(scores.insert(Preset::Beginner, fast), Some(1));
+    /// assert_eq!(scores.table(Preset::Beginner)[1].name, "slow");
+    /// ```
+    pub fn insert(&mut self, preset: Preset, entry: ScoreEntry) -> Option<usize> {
+        if !self.qualifies(preset, entry.time) {
+            return None;
+        }
+        let table = self.tables.entry(preset.name().to_string()).or_default();
+        let rank = table.partition_point(|existing| existing.time <= entry.time);
+        table.insert(rank, entry);
+        table.truncate(TABLE_SIZE);
+        Some(rank + 1)
+    }
+
+    /// Clears the table for the preset, or every table if no preset is given
+    pub fn reset(&mut self, preset: Option<Preset>) {
+        match preset {
+            Some(preset) => {
+                self.tables.remove(preset.name());
+            }
+            None => self.tables.clear(),
+        }
+    }
+
+    /// Turns the high scores into the tab separated file format. One entry per line
+    fn serialize(&self) -> String {
+        let mut out = String::from("# preset\tname\ttime_ms\tdate\tseed\t3bv_per_s\n");
+        for (preset, table) in self.tables.iter() {
+            for entry in table.iter() {
+                out.push_str(&format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\n",
+                    preset,
+                    entry.name,
+                    entry.time.as_millis(),
+                    entry.date,
+                    entry.seed,
+                    entry.three_bv_per_second
+                ));
+            }
+        }
+        out
+    }
+
+    /// Parses the tab separated file format
+    fn parse(contents: &str) -> io::Result<HighScores> {
+        let invalid = |line: usize| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid high score on line {}", line + 1),
+            )
+        };
+        let mut scores = HighScores::default();
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 6 {
+                return Err(invalid(index));
+            }
+            let preset = Preset::from_name(fields[0]).ok_or_else(|| invalid(index))?;
+            let entry = ScoreEntry {
+                name: fields[1].to_string(),
+                time: Duration::from_millis(fields[2].parse().map_err(|_| invalid(index))?),
+                date: fields[3].parse().map_err(|_| invalid(index))?,
+                seed: fields[4].parse().map_err(|_| invalid(index))?,
+                three_bv_per_second: fields[5].parse().map_err(|_| invalid(index))?,
+            };
+            scores.insert(preset, entry);
+        }
+        Ok(scores)
+    }
+}
+
+/// Displays every preset's table
+impl fmt::Display for HighScores {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, preset) in Preset::ALL.into_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", ScoreTable(preset, self.table(preset)))?;
+        }
+        Ok(())
+    }
+}
+
+/// A single preset's table, used to display one table at a time
+pub struct ScoreTable<'a>(pub Preset, pub &'a [ScoreEntry]);
+
+/// Displays the table with one line per entry
+impl fmt::Display for ScoreTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ScoreTable(preset, table) = self;
+        writeln!(f, "{} high scores", preset.name())?;
+        if table.is_empty() {
+            return writeln!(f, "  no wins yet");
+        }
+        writeln!(
+            f,
+            "{:>4}  {:<20} {:>9} {:>10} {:>20} {:>6}",
+            "rank", "name", "time", "date", "seed", "3BV/s"
+        )?;
+        for (rank, entry) in table.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}  {:<20} {:>8.2}s {:>10} {:>20} {:>6.2}",
+                rank + 1,
+                entry.name,
+                entry.time.as_secs_f64(),
+                entry.date,
+                entry.seed,
+                entry.three_bv_per_second
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, seconds: u64) -> ScoreEntry {
+        let date = Date {
+            year: 2024,
+            month: 5,
+            day: 1,
+        };
+        ScoreEntry::new(name, Duration::from_secs(seconds), date, seconds, 1.25)
+    }
+
+    #[test]
+    fn test_table_keeps_fastest_entries() {
+        let mut scores = HighScores::default();
+        for seconds in (1..=TABLE_SIZE as u64 + 5).rev() {
+            scores.insert(Preset::Expert, entry("player", seconds * 10));
+        }
+        let table = scores.table(Preset::Expert);
+        assert_eq!(table.len(), TABLE_SIZE);
+        assert_eq!(table[0].time, Duration::from_secs(10));
+        assert!(!scores.qualifies(Preset::Expert, Duration::from_secs(1000)));
+        assert_eq!(scores.insert(Preset::Expert, entry("late", 1000)), None);
+        assert!(scores.table(Preset::Beginner).is_empty());
+    }
+
+    #[test]
+    fn test_update_round_trip_and_reset() {
+        let path = std::env::temp_dir()
+            .join(format!("rms-scores-{}", std::process::id()))
+            .join(SCORES_FILE);
+        HighScores::update(&path, |scores| {
+            scores.insert(Preset::Beginner, entry("first", 30));
+            scores.insert(Preset::Expert, entry("second", 300));
+        })
+        .unwrap();
+        let loaded = HighScores::load_from(&path).unwrap();
+        assert_eq!(loaded.table(Preset::Beginner), &[entry("first", 30)]);
+
+        HighScores::update(&path, |scores| scores.reset(Some(Preset::Beginner))).unwrap();
+        let loaded = HighScores::load_from(&path).unwrap();
+        assert!(loaded.table(Preset::Beginner).is_empty());
+        assert_eq!(loaded.table(Preset::Expert).len(), 1);
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{Board, MinesweeperSquare};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a game may go untouched before it is removed
+pub const DEFAULT_EXPIRY: Duration = Duration::from_secs(30 * 60);
+/// Most games held at once, so clients can not use up all of the server's memory
+pub const DEFAULT_MAX_SESSIONS: usize = 10_000;
+
+/// A game held by the session manager
+pub struct Session {
+    pub board: Board<MinesweeperSquare>,
+    pub seed: u64,
+    /// Set once a mine has been revealed
+    pub lost: bool,
+    last_used: Instant,
+}
+
+impl Session {
+    /// Returns true if the game has ended either way
+    pub fn is_over(&self) -> bool {
+        self.lost || self.board.is_won().is_some()
+    }
+}
+
+/// Holds many concurrent games by id. Games that have not been used for the expiry time are removed
+pub struct SessionManager {
+    sessions: Mutex<HashMap<String, Session>>,
+    expiry: Duration,
+    max_sessions: usize,
+}
+
+impl Default for SessionManager {
+    fn default() -> Self {
+        SessionManager::new(DEFAULT_EXPIRY, DEFAULT_MAX_SESSIONS)
+    }
+}
+
+impl SessionManager {
+    /// Creates a manager that removes games after the expiry and holds at most max_sessions games
+    pub fn new(expiry: Duration, max_sessions: usize) -> SessionManager {
+        SessionManager {
+            sessions: Mutex::new(HashMap::new()),
+            expiry,
+            max_sessions,
+        }
+    }
+
+    /// Adds a new game and returns its id. Returns an error if the manager is full
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::session::SessionManager;
+    /// use rusty_mine_sweeper::Preset;
+    /// let sessions = SessionManager::default();
+    /// let id = sessions.create(Preset::Beginner.board_with_seed(1), 1).unwrap();
+    ///
+    /// assert_eq!(sessions.with_session(&id, |session| session.seed), Some(1));
+    /// assert!(sessions.remove(&id));
+    /// assert_eq!(sessions.with_session(&id, |session| session.seed), None);
+    /// ```
+    pub fn create(
+        &self,
+        board: Board<MinesweeperSquare>,
+        seed: u64,
+    ) -> Result<String, &'static str> {
+        let mut sessions = self.lock();
+        self.remove_expired(&mut sessions);
+        if sessions.len() >= self.max_sessions {
+            return Err("Too many games in progress. Try again later");
+        }
+        let id = loop {
+            let id = format!(
+                "{:016x}{:016x}",
+                rand::random::<u64>(),
+                rand::random::<u64>()
+            );
+            if !sessions.contains_key(&id) {
+                break id;
+            }
+        };
+        sessions.insert(
+            id.clone(),
+            Session {
+                board,
+                seed,
+                lost: false,
+                last_used: Instant::now(),
+            },
+        );
+        Ok(id)
+    }
+
+    /// Runs the closure on the game with the id, marking it as used.
+    /// Returns None if there is no such game or it has expired
+    pub fn with_session<T>(&self, id: &str, f: impl FnOnce(&mut Session) -> T) -> Option<T> {
+        let mut sessions = self.lock();
+        self.remove_expired(&mut sessions);
+        let session = sessions.get_mut(id)?;
+        session.last_used = Instant::now();
+        Some(f(session))
+    }
+
+    /// Removes the game with the id. Returns true if it existed
+    pub fn remove(&self, id: &str) -> bool {
+        self.lock().remove(id).is_some()
+    }
+
+    /// Number of games currently held
+    pub fn len(&self) -> usize {
+        let mut sessions = self.lock();
+        self.remove_expired(&mut sessions);
+        sessions.len()
+    }
+
+    /// Returns true if no games are held
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Session>> {
+        match self.sessions.lock() {
+            Ok(sessions) => sessions,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn remove_expired(&self, sessions: &mut HashMap<String, Session>) {
+        sessions.retain(|_, session| session.last_used.elapsed() < self.expiry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Preset;
+
+    #[test]
+    fn test_games_expire_and_capacity_is_limited() {
+        let sessions = SessionManager::new(Duration::from_millis(50), 2);
+        let first = sessions.create(Preset::Beginner.board(), 0).unwrap();
+        sessions.create(Preset::Beginner.board(), 0).unwrap();
+        assert!(sessions.create(Preset::Beginner.board(), 0).is_err());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(sessions.is_empty());
+        assert!(sessions.with_session(&first, |_| ()).is_none());
+        assert!(sessions.create(Preset::Beginner.board(), 0).is_ok());
+    }
+}
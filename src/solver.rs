@@ -0,0 +1,243 @@
+use crate::cell::CellState;
+use crate::view::PlayerView;
+use std::collections::BTreeSet;
+
+type Cord = (usize, usize);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Squares that can be proven safe or proven to be mines from the revealed numbers alone
+pub struct Deductions {
+    pub safe: BTreeSet<Cord>,
+    pub mines: BTreeSet<Cord>,
+}
+
+impl Deductions {
+    /// Returns true if nothing could be deduced
+    pub fn is_empty(&self) -> bool {
+        self.safe.is_empty() && self.mines.is_empty()
+    }
+}
+
+/// A revealed number and the hidden squares around it that must hold the remaining mines
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Constraint {
+    squares: BTreeSet<Cord>,
+    mines: usize,
+}
+
+/// Builds one constraint per revealed number that still has hidden squares around it.
+/// Flagged squares are treated as known mines
+fn constraints(view: &PlayerView) -> Vec<Constraint> {
+    let mut constraints = Vec::new();
+    for cell in view.cells() {
+        let Some(number) = cell.number() else {
+            continue;
+        };
+        let mut squares = BTreeSet::new();
+        let mut flags = 0;
+        for neighbour in view.neighbour_cells(cell.x, cell.y) {
+            match neighbour.state {
+                CellState::Hidden => {
+                    squares.insert((neighbour.x, neighbour.y));
+                }
+                CellState::Flagged(count) => flags += count as usize,
+                CellState::Revealed(_) | CellState::Mine(_) | CellState::Void => {}
+            }
+        }
+        if squares.is_empty() {
+            continue;
+        }
+        constraints.push(Constraint {
+            squares,
+            mines: (number as usize).saturating_sub(flags),
+        });
+    }
+    constraints
+}
+
+/// Finds every square that can be proven safe or a mine. Uses the single square rules (a number
+/// whose mines are all accounted for, or whose hidden squares must all be mines) and the subset
+/// rule between pairs of numbers whose hidden squares overlap. The rules assume a square holds
+/// at most one mine, so they do not apply to boards made with place_multi_mines
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::solver::deduce;
+/// use rusty_mine_sweeper::view::PlayerView;
+/// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+/// let mut board: Board<MinesweeperSquare> = Board::isize_board(4, 1);
+/// let mut mine = MinesweeperSquare::new();
+/// mine.set_is_mine(true);
+/// board.set(2, 0, mine);
+/// board.reveal(0, 0).unwrap();
+///
+/// // the 1 at (1, 0) only touches one hidden square
+/// let deductions = deduce(&PlayerView::new(&board));
+/// assert!(deductions.mines.contains(&(2, 0)));
+/// ```
+pub fn deduce(view: &PlayerView) -> Deductions {
+    let constraints = constraints(view);
+    let deductions = single_square_rules(&constraints);
+    if !deductions.is_empty() {
+        return deductions;
+    }
+    subset_rule(&constraints)
+}
+
+/// Finds the squares that can be proven with the single square rules alone: a number whose mines
+/// are all accounted for, or whose hidden squares must all be mines
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::solver::deduce_simple;
+/// use rusty_mine_sweeper::view::PlayerView;
+/// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+/// let mut board: Board<MinesweeperSquare> = Board::isize_board(4, 1);
+/// let mut mine = MinesweeperSquare::new();
+/// mine.set_is_mine(true);
+/// board.set(2, 0, mine);
+/// board.reveal(0, 0).unwrap();
+///
+/// assert!(deduce_simple(&PlayerView::new(&board)).mines.contains(&(2, 0)));
+/// ```
+pub fn deduce_simple(view: &PlayerView) -> Deductions {
+    single_square_rules(&constraints(view))
+}
+
+/// Applies the single square rules to each number on its own
+fn single_square_rules(constraints: &[Constraint]) -> Deductions {
+    let mut deductions = Deductions::default();
+    for constraint in constraints.iter() {
+        if constraint.mines == 0 {
+            deductions.safe.extend(constraint.squares.iter().copied());
+        } else if constraint.mines == constraint.squares.len() {
+            deductions.mines.extend(constraint.squares.iter().copied());
+        }
+    }
+    deductions
+}
+
+/// Applies the subset rule between each pair of numbers where one's hidden squares are all
+/// around the other
+fn subset_rule(constraints: &[Constraint]) -> Deductions {
+    let mut deductions = Deductions::default();
+    for smaller in constraints.iter() {
+        for larger in constraints.iter() {
+            if smaller.squares.len() >= larger.squares.len()
+                || !smaller.squares.is_subset(&larger.squares)
+            {
+                continue;
+            }
+            let rest: Vec<Cord> = larger
+                .squares
+                .difference(&smaller.squares)
+                .copied()
+                .collect();
+            let rest_mines = larger.mines.saturating_sub(smaller.mines);
+            if rest_mines == 0 {
+                deductions.safe.extend(rest);
+            } else if rest_mines == rest.len() {
+                deductions.mines.extend(rest);
+            }
+        }
+    }
+    deductions
+}
+
+/// Estimates the chance of each hidden, unflagged square being a mine.
+/// Squares next to a number use the highest local density of the numbers around them and every
+/// other square uses the density of the mines left over the hidden squares left
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::solver::mine_probabilities;
+/// use rusty_mine_sweeper::view::PlayerView;
+/// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+/// let board: Board<MinesweeperSquare> = Board::isize_board(3, 3);
+///
+/// // nothing revealed and no mines so every square is safe
+/// let chances = mine_probabilities(&PlayerView::new(&board));
+/// assert!(chances.iter().all(|(_, chance)| *chance == 0.0));
+/// ```
+pub fn mine_probabilities(view: &PlayerView) -> Vec<(Cord, f64)> {
+    let constraints = constraints(view);
+    let hidden: Vec<Cord> = view.hidden_cells().map(|cell| (cell.x, cell.y)).collect();
+    let flags: usize = view
+        .flagged_cells()
+        .map(|cell| match cell.state {
+            CellState::Flagged(count) => count as usize,
+            _ => 0,
+        })
+        .sum();
+    if hidden.is_empty() {
+        return Vec::new();
+    }
+    let remaining = view.mine_count().saturating_sub(flags) as f64;
+    let density = (remaining / hidden.len() as f64).min(1.0);
+
+    hidden
+        .into_iter()
+        .map(|cord| {
+            let local = constraints
+                .iter()
+                .filter(|constraint| constraint.squares.contains(&cord))
+                .map(|constraint| constraint.mines as f64 / constraint.squares.len() as f64)
+                .fold(None, |highest: Option<f64>, chance| {
+                    Some(highest.map_or(chance, |highest| highest.max(chance)))
+                });
+            (cord, local.unwrap_or(density))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Board, MinesweeperSquare, Neighbourhood, Topology};
+
+    fn board_with_mines(width: usize, height: usize, mines: &[Cord]) -> Board<MinesweeperSquare> {
+        let mut board = Board::isize_board(width, height);
+        for &(x, y) in mines {
+            board.change_mines(x, y, 1);
+        }
+        board
+    }
+
+    #[test]
+    fn test_subset_rule() {
+        // 0 0 0
+        // 1 2 1   mines under the two outer hidden squares of the bottom row
+        // ? ? ?
+        let mut board = board_with_mines(3, 3, &[(0, 2), (2, 2)]);
+        board.reveal(0, 0).unwrap();
+        let deductions = deduce(&PlayerView::new(&board));
+        assert!(deductions.safe.is_empty());
+        assert_eq!(deductions.mines, BTreeSet::from([(0, 2), (2, 2)]));
+
+        board.toggle_flag(0, 2).unwrap();
+        board.toggle_flag(2, 2).unwrap();
+        assert_eq!(
+            deduce(&PlayerView::new(&board)).safe,
+            BTreeSet::from([(1, 2)])
+        );
+    }
+
+    #[test]
+    fn test_knight_neighbourhood() {
+        // the only square a knight's move from (0, 0) on a 3x2 board is (2, 1)
+        let mut board =
+            Board::isize_board_with_neighbourhood(3, 2, Topology::Bounded, Neighbourhood::Knight);
+        board.change_mines(2, 1, 1);
+        board.reveal(0, 0).unwrap();
+        let deductions = deduce(&PlayerView::new(&board));
+        assert_eq!(deductions.mines, BTreeSet::from([(2, 1)]));
+        assert!(deductions.safe.is_empty());
+    }
+
+    #[test]
+    fn test_probabilities_prefer_unconstrained_squares() {
+        let mut board = board_with_mines(4, 1, &[(1, 0)]);
+        board.reveal(0, 0).unwrap();
+        let chances = mine_probabilities(&PlayerView::new(&board));
+        let chance_of = |cord: Cord| chances.iter().find(|(c, _)| *c == cord).unwrap().1;
+        assert_eq!(chance_of((1, 0)), 1.0);
+        assert!(chance_of((3, 0)) < 1.0);
+    }
+}
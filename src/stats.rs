@@ -0,0 +1,361 @@
+use crate::report::GameReport;
+use crate::storage;
+use crate::Preset;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Name of the statistics file inside the data directory
+const STATS_FILE: &str = "stats.tsv";
+
+/// Returns the category a game is recorded under. Preset boards use the preset name and
+/// every other board is grouped by its size
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::stats::category;
+///
+/// assert_eq!(category(9, 9, 10), "beginner");
+/// assert_eq!(category(12, 7, 10), "custom 12x7");
+/// ```
+pub fn category(width: usize, height: usize, mines: usize) -> String {
+    match Preset::matching(width, height, mines) {
+        Some(preset) => preset.name().to_string(),
+        None => format!("custom {}x{}", width, height),
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Running totals for a single category of games
+pub struct GameStats {
+    pub played: usize,
+    pub won: usize,
+    pub lost: usize,
+    pub current_streak: usize,
+    pub best_streak: usize,
+    pub best_time: Option<Duration>,
+    /// Sum of 3BV/s over every won game, used for the average
+    pub three_bv_per_second_total: f64,
+}
+
+impl GameStats {
+    /// Adds the result of a finished game to the totals
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::report::{ClickCounts, GameReport};
+    /// use rusty_mine_sweeper::stats::GameStats;
+    /// use std::time::Duration;
+    /// let win = GameReport {
+    ///     won: true,
+    ///     elapsed: Duration::from_secs(10),
+    ///     clicks: ClickCounts { reveals: 20, flags: 0, chords: 0 },
+    ///     three_bv: 20,
+    ///     three_bv_solved: 20,
+    ///     width: 9,
+    ///     height: 9,
+    ///     mines: 10,
+    /// };
+    /// let mut stats = GameStats::default();
+    /// stats.record(&win);
+    /// stats.record(&win);
+    ///
+    /// assert_eq!(stats.won, 2);
+    /// assert_eq!(stats.best_streak, 2);
+    /// assert_eq!(stats.average_three_bv_per_second(), 2.0);
+    /// ```
+    pub fn record(&mut self, report: &GameReport) {
+        self.played += 1;
+        if !report.won {
+            self.lost += 1;
+            self.current_streak = 0;
+            return;
+        }
+        self.won += 1;
+        self.current_streak += 1;
+        self.best_streak = self.best_streak.max(self.current_streak);
+        if self.best_time.is_none_or(|best| report.elapsed < best) {
+            self.best_time = Some(report.elapsed);
+        }
+        self.three_bv_per_second_total += report.three_bv_per_second();
+    }
+
+    /// Average 3BV/s over the won games. 0 if no games have been won
+    pub fn average_three_bv_per_second(&self) -> f64 {
+        if self.won == 0 {
+            return 0.0;
+        }
+        self.three_bv_per_second_total / self.won as f64
+    }
+
+    /// Percentage of games played that were won
+    pub fn win_rate(&self) -> f64 {
+        if self.played == 0 {
+            return 0.0;
+        }
+        self.won as f64 / self.played as f64 * 100.0
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// Statistics for every category of game that has been played, saved between runs
+pub struct Statistics {
+    categories: BTreeMap<String, GameStats>,
+}
+
+impl Statistics {
+    /// Path of the statistics file in the data directory
+    pub fn default_path() -> PathBuf {
+        storage::data_dir().join(STATS_FILE)
+    }
+
+    /// Loads the statistics from the default path
+    pub fn load() -> io::Result<Statistics> {
+        Statistics::load_from(&Statistics::default_path())
+    }
+
+    /// Saves the statistics to the default path
+    pub fn save(&self) -> io::Result<()> {
+        self.save_to(&Statistics::default_path())
+    }
+
+    /// Loads the statistics from the given file. A missing file gives empty statistics
+    pub fn load_from(path: &Path) -> io::Result<Statistics> {
+        Statistics::parse(&storage::read_or_empty(path)?)
+    }
+
+    /// Saves the statistics to the given file, replacing it
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        storage::write_atomic(path, &self.serialize())
+    }
+
+    /// Loads the statistics, applies the change and saves them again while holding a lock on
+    /// the file, so games finishing at the same time can not overwrite each other's results
+    pub fn update<T>(path: &Path, change: impl FnOnce(&mut Statistics) -> T) -> io::Result<T> {
+        storage::with_lock(path, || {
+            let mut stats = Statistics::load_from(path)?;
+            let result = change(&mut stats);
+            stats.save_to(path)?;
+            Ok(result)
+        })
+    }
+
+    /// Records a finished game under its category
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::report::{ClickCounts, GameReport};
+    /// use rusty_mine_sweeper::stats::Statistics;
+    /// use std::time::Duration;
+    /// let loss = GameReport {
+    ///     won: false,
+    ///     elapsed: Duration::from_secs(3),
+    ///     clicks: ClickCounts { reveals: 1, flags: 0, chords: 0 },
+    ///     three_bv: 20,
+    ///     three_bv_solved: 0,
+    ///     width: 9,
+    ///     height: 9,
+    ///     mines: 10,
+    /// };
+    /// let mut stats = Statistics::default();
+    /// stats.record(&loss);
+    ///
+    /// assert_eq!(stats.get("beginner").unwrap().lost, 1);
+    /// ```
+    pub fn record(&mut self, report: &GameReport) {
+        self.record_as(&category(report.width, report.height, report.mines), report);
+    }
+
+    /// Records a finished game under the given category name
+    pub fn record_as(&mut self, category: &str, report: &GameReport) {
+        self.categories
+            .entry(category.to_string())
+            .or_default()
+            .record(report);
+    }
+
+    /// Gets the statistics for a category
+    pub fn get(&self, category: &str) -> Option<&GameStats> {
+        self.categories.get(category)
+    }
+
+    /// Iterates over every category and its statistics in name order
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &GameStats)> {
+        self.categories.iter()
+    }
+
+    /// Returns true if no games have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    /// Turns the statistics into the tab separated file format. One category per line
+    fn serialize(&self) -> String {
+        let mut out = String::from(
+            "# category\tplayed\twon\tlost\tcurrent_streak\tbest_streak\tbest_time_ms\t3bv_per_s_total\n",
+        );
+        for (name, stats) in self.categories.iter() {
+            let best_time = stats
+                .best_time
+                .map_or("-".to_string(), |time| time.as_millis().to_string());
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+                name,
+                stats.played,
+                stats.won,
+                stats.lost,
+                stats.current_streak,
+                stats.best_streak,
+                best_time,
+                stats.three_bv_per_second_total
+            ));
+        }
+        out
+    }
+
+    /// Parses the tab separated file format
+    fn parse(contents: &str) -> io::Result<Statistics> {
+        let invalid = |line: usize| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid statistics on line {}", line + 1),
+            )
+        };
+        let mut categories = BTreeMap::new();
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 8 {
+                return Err(invalid(index));
+            }
+            let number = |field: &str| field.parse::<usize>().map_err(|_| invalid(index));
+            let best_time = match fields[6] {
+                "-" => None,
+                ms => Some(Duration::from_millis(
+                    ms.parse::<u64>().map_err(|_| invalid(index))?,
+                )),
+            };
+            let stats = GameStats {
+                played: number(fields[1])?,
+                won: number(fields[2])?,
+                lost: number(fields[3])?,
+                current_streak: number(fields[4])?,
+                best_streak: number(fields[5])?,
+                best_time,
+                three_bv_per_second_total: fields[7].parse().map_err(|_| invalid(index))?,
+            };
+            categories.insert(fields[0].to_string(), stats);
+        }
+        Ok(Statistics { categories })
+    }
+}
+
+/// Displays the statistics as a table with one row per category
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "No games played yet");
+        }
+        writeln!(
+            f,
+            "{:<16} {:>6} {:>5} {:>5} {:>6} {:>7} {:>7} {:>10} {:>9}",
+            "category",
+            "played",
+            "won",
+            "lost",
+            "win %",
+            "streak",
+            "best",
+            "best time",
+            "avg 3BV/s"
+        )?;
+        for (name, stats) in self.iter() {
+            let best_time = stats.best_time.map_or("-".to_string(), |time| {
+                format!("{:.2}s", time.as_secs_f64())
+            });
+            writeln!(
+                f,
+                "{:<16} {:>6} {:>5} {:>5} {:>6.1} {:>7} {:>7} {:>10} {:>9.2}",
+                name,
+                stats.played,
+                stats.won,
+                stats.lost,
+                stats.win_rate(),
+                stats.current_streak,
+                stats.best_streak,
+                best_time,
+                stats.average_three_bv_per_second()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::ClickCounts;
+
+    fn report(won: bool, seconds: u64) -> GameReport {
+        GameReport {
+            won,
+            elapsed: Duration::from_secs(seconds),
+            clicks: ClickCounts {
+                reveals: 10,
+                flags: 0,
+                chords: 0,
+            },
+            three_bv: 10,
+            three_bv_solved: 10,
+            width: 12,
+            height: 7,
+            mines: 10,
+        }
+    }
+
+    #[test]
+    fn test_streaks_and_best_time() {
+        let mut stats = GameStats::default();
+        for (won, seconds) in [(true, 9), (true, 5), (false, 1), (true, 7)] {
+            stats.record(&report(won, seconds));
+        }
+        assert_eq!(stats.played, 4);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.best_streak, 2);
+        assert_eq!(stats.best_time, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("rms-stats-{}", std::process::id()))
+            .join(STATS_FILE);
+        let mut stats = Statistics::default();
+        stats.record(&report(true, 4));
+        stats.record(&report(false, 2));
+        stats.save_to(&path).unwrap();
+
+        let loaded = Statistics::load_from(&path).unwrap();
+        assert_eq!(loaded, stats);
+        assert_eq!(loaded.get("custom 12x7").unwrap().played, 2);
+
+        // games finishing at once each hold the lock, so none of them are lost
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    Statistics::update(&path, |stats| stats.record(&report(true, 3))).unwrap()
+                });
+            }
+        });
+        let loaded = Statistics::load_from(&path).unwrap();
+        assert_eq!(loaded.get("custom 12x7").unwrap().played, 6);
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_lines() {
+        assert!(Statistics::parse("beginner\t1\t2\n").is_err());
+        assert!(Statistics::parse("").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,124 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Environment variable that overrides where the game keeps its files. Mostly used by tests
+pub const DATA_DIR_ENV: &str = "RUSTY_MINE_SWEEPER_DATA_DIR";
+
+/// Name of the folder created inside the user's data directory
+const APP_DIR: &str = "rusty-mine-sweeper";
+
+/// Makes the temporary file names used by write_atomic unique within the process
+static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the directory the game stores its files in.
+/// Uses $RUSTY_MINE_SWEEPER_DATA_DIR if set, otherwise the platform's user data directory
+/// ($XDG_DATA_HOME or ~/.local/share on unix, %APPDATA% on windows). Falls back to the current directory
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::storage::data_dir;
+///
+/// let dir = data_dir();
+/// assert!(!dir.as_os_str().is_empty());
+/// ```
+pub fn data_dir() -> PathBuf {
+    if let Some(dir) = env::var_os(DATA_DIR_ENV).filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(dir);
+    }
+    let base = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+    base.unwrap_or_else(|| PathBuf::from(".")).join(APP_DIR)
+}
+
+/// Reads the file at the given path to a string. A missing file is treated as empty
+pub fn read_or_empty(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes the contents to a temporary file next to the path and then renames it into place,
+/// so a crash part way through never leaves a half written file behind
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let tmp = PathBuf::from(tmp);
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
+
+/// Runs the closure while holding an exclusive lock on a ".lock" file next to the path.
+/// Other processes calling this for the same path wait until the lock is released, so a
+/// read-modify-write of the file inside the closure can not interleave with another run
+pub fn with_lock<T>(path: &Path, f: impl FnOnce() -> io::Result<T>) -> io::Result<T> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut lock_path = path.as_os_str().to_owned();
+    lock_path.push(".lock");
+    let lock = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(PathBuf::from(lock_path))?;
+    lock.lock()?;
+    let result = f();
+    lock.unlock()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomic_creates_parent_and_replaces() {
+        let dir = env::temp_dir().join(format!("rms-storage-{}", std::process::id()));
+        let path = dir.join("nested").join("file.txt");
+        write_atomic(&path, "one").unwrap();
+        write_atomic(&path, "two").unwrap();
+        assert_eq!(read_or_empty(&path).unwrap(), "two");
+        assert_eq!(read_or_empty(&dir.join("missing")).unwrap(), "");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_with_lock_serializes_updates() {
+        let dir = env::temp_dir().join(format!("rms-lock-{}", std::process::id()));
+        let path = dir.join("counter.txt");
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    with_lock(&path, || {
+                        let count: usize = read_or_empty(&path)?.parse().unwrap_or(0);
+                        write_atomic(&path, &(count + 1).to_string())
+                    })
+                    .unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(read_or_empty(&path).unwrap(), "8");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,259 @@
+use crate::report::{ClickCounts, GameReport};
+use crate::view::PlayerView;
+use crate::{
+    parse_square, Board, MinesweeperSquare, Preset, RevealOutcome, ACTION_MENU, COLUMN_PROMPT,
+    PRESET_MENU, ROW_PROMPT,
+};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a player may take to answer a prompt before they are disconnected
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// Most players connected at once. Further connections are told to try again later
+pub const MAX_CONNECTIONS: usize = 64;
+/// Longest line read from a player
+const MAX_LINE_LENGTH: usize = 256;
+/// Largest width or height of a custom board
+const MAX_SIZE: usize = 99;
+
+/// A line based text connection to a player, such as a netcat or telnet session
+pub struct Client<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> Client<R, W> {
+    pub fn new(reader: R, writer: W) -> Client<R, W> {
+        Client { reader, writer }
+    }
+
+    /// Writes the text to the player
+    pub fn send(&mut self, text: &str) -> io::Result<()> {
+        self.writer.write_all(text.as_bytes())?;
+        self.writer.flush()
+    }
+
+    /// Writes the prompt and reads back a line. Fails once the player disconnects or times out
+    /// # Examples
+    /// ```
+    /// use rusty_mine_sweeper::tcp::Client;
+    /// let mut output = Vec::new();
+    /// let mut client = Client::new("b\r\n".as_bytes(), &mut output);
+    ///
+    /// assert_eq!(client.prompt("Row: ").unwrap(), "b");
+    /// assert!(client.prompt("Row: ").is_err());
+    /// assert_eq!(output, b"Row: Row: ");
+    /// ```
+    pub fn prompt(&mut self, prompt: &str) -> io::Result<String> {
+        self.send(prompt)?;
+        let mut line = String::new();
+        let read = (&mut self.reader)
+            .take(MAX_LINE_LENGTH as u64)
+            .read_line(&mut line)?;
+        if read == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "player disconnected",
+            ));
+        }
+        if !line.ends_with('\n') && read == MAX_LINE_LENGTH {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "line is too long",
+            ));
+        }
+        Ok(line.trim().to_string())
+    }
+
+    /// Asks for a row and column until the player picks a square on the board.
+    /// Returns the (x, y) cords of the square
+    pub fn read_square(&mut self, width: usize, height: usize) -> io::Result<(usize, usize)> {
+        loop {
+            let row = self.prompt(ROW_PROMPT)?;
+            let col = self.prompt(COLUMN_PROMPT)?;
+            match parse_square(&row, &col, width, height) {
+                Ok((row_index, col_index)) => return Ok((col_index, row_index)),
+                Err(e) => self.send(&format!("{}\n", e))?,
+            }
+        }
+    }
+
+    /// Asks the player for a number between 1 and max, repeating the prompt until they give one
+    fn read_number(&mut self, prompt: &str, max: usize) -> io::Result<usize> {
+        loop {
+            match self.prompt(prompt)?.parse::<usize>() {
+                Ok(number) if (1..=max).contains(&number) => return Ok(number),
+                _ => self.send(&format!("Invalid input. Please enter 1..{}.\n", max))?,
+            }
+        }
+    }
+
+    /// Asks the player for a preset or a custom board and creates it
+    pub fn choose_board(&mut self) -> io::Result<Board<MinesweeperSquare>> {
+        let preset = match self.read_number(PRESET_MENU, 4)? {
+            1 => Preset::Beginner,
+            2 => Preset::Intermediate,
+            3 => Preset::Expert,
+            _ => {
+                let width =
+                    self.read_number("Enter the width you wish for the board\n", MAX_SIZE)?;
+                let height =
+                    self.read_number("Enter the height you wish for the board\n", MAX_SIZE)?;
+                let level = self.read_number(
+                    "Select a difficulty level, up to 9. Each level = 10% more mines on board\n",
+                    9,
+                )?;
+                let mut board = Board::isize_board(width, height);
+                for _ in 0..level {
+                    board.increase_difficulty();
+                }
+                return Ok(board);
+            }
+        };
+        Ok(preset.board())
+    }
+
+    /// Plays the board until it is won or lost, returning the end of game report
+    pub fn play_game(&mut self, board: Board<MinesweeperSquare>) -> io::Result<GameReport> {
+        self.play_game_watched(board, |_| {})
+    }
+
+    /// Plays the board like play_game, calling watch with the board after every move that
+    /// changed it
+    pub fn play_game_watched(
+        &mut self,
+        mut board: Board<MinesweeperSquare>,
+        mut watch: impl FnMut(&Board<MinesweeperSquare>),
+    ) -> io::Result<GameReport> {
+        let mut clicks = ClickCounts::default();
+        let mut started: Option<Instant> = None;
+        self.send(&format!("{}\n", PlayerView::new(&board)))?;
+        loop {
+            if board.is_won().is_some() {
+                self.send("You won!\n")?;
+                break;
+            }
+            let action = self.read_number(ACTION_MENU, 3)?;
+            started.get_or_insert_with(Instant::now);
+            let (x, y) = self.read_square(board.width, board.height)?;
+            let (outcome, after) = match action {
+                1 => {
+                    clicks.flags += 1;
+                    let outcome = board.toggle_flag(x, y).map(|_| RevealOutcome::Revealed(0));
+                    (outcome, "mark/flag")
+                }
+                2 => {
+                    clicks.reveals += 1;
+                    (board.reveal(x, y), "move")
+                }
+                _ => {
+                    clicks.chords += 1;
+                    (board.chord(x, y), "chord")
+                }
+            };
+            match outcome {
+                Ok(RevealOutcome::Mine) => {
+                    self.send(&format!("{}\nYou lose\n", PlayerView::new(&board)))?;
+                    break;
+                }
+                Ok(RevealOutcome::Revealed(_)) => {
+                    self.send(&format!(
+                        "Board after your {}:\n{}\n",
+                        after,
+                        PlayerView::new(&board)
+                    ))?;
+                    watch(&board);
+                }
+                Err(e) => self.send(&format!("Invalid position selection. {}\n", e))?,
+            }
+        }
+        let elapsed = started.map(|start| start.elapsed()).unwrap_or_default();
+        Ok(GameReport::new(&board, clicks, elapsed))
+    }
+
+    /// Runs a whole session: games are played one after the other until the player stops
+    pub fn run(&mut self) -> io::Result<()> {
+        self.send("Welcome to rusty mine sweeper! Please note that all mines MUST be marked/flagged in order to win the game\n")?;
+        loop {
+            let board = self.choose_board()?;
+            let report = self.play_game(board)?;
+            self.send(&format!("{}\n", report))?;
+            let again = self.prompt("Play again? (y/n) ")?;
+            if !again.eq_ignore_ascii_case("y") {
+                return self.send("Thanks for playing!\n");
+            }
+        }
+    }
+}
+
+/// Plays a session over the connection, telling the player when they are dropped for being idle
+fn handle_connection(stream: TcpStream, idle_timeout: Duration) -> io::Result<()> {
+    stream.set_read_timeout(Some(idle_timeout))?;
+    let mut client = Client::new(BufReader::new(stream.try_clone()?), &stream);
+    match client.run() {
+        Err(e)
+            if matches!(
+                e.kind(),
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ) =>
+        {
+            client.send("\nDisconnected after being idle for too long\n")
+        }
+        result => result,
+    }
+}
+
+/// Accepts connections on the listener forever, playing each on its own thread
+pub fn serve(listener: TcpListener, idle_timeout: Duration) -> io::Result<()> {
+    let connections = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+        if connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            connections.fetch_sub(1, Ordering::SeqCst);
+            let _ = stream.write_all(b"The server is full. Try again later\n");
+            continue;
+        }
+        let connections = Arc::clone(&connections);
+        thread::spawn(move || {
+            // A player hanging up only ends their own session
+            let _ = handle_connection(stream, idle_timeout);
+            connections.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_plays_a_game_from_typed_lines() {
+        // beginner board, select a1, then hang up
+        let mut output = Vec::new();
+        let mut client = Client::new("1\n2\na\n1\n".as_bytes(), &mut output);
+        assert!(client.run().is_err());
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(PRESET_MENU));
+        assert!(output.contains(ACTION_MENU));
+        assert!(output.contains(ROW_PROMPT));
+        assert!(output.contains("Board after your move:") || output.contains("You lose"));
+    }
+
+    #[test]
+    fn test_invalid_input_is_reported_and_asked_again() {
+        let mut output = Vec::new();
+        let mut client = Client::new("7\n1\nz\n1\n".as_bytes(), &mut output);
+        assert!(client.run().is_err());
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Invalid input. Please enter 1..4."));
+        assert!(output.contains("Invalid input. Please enter 1..3."));
+    }
+}
@@ -0,0 +1,165 @@
+//! What a player is allowed to know about a board.
+//!
+//! A Board<MinesweeperSquare> knows where every mine is, so anything handed one can cheat by
+//! asking a hidden square whether it is a mine. A PlayerView is a copy of the board with the
+//! mines under squares that have not been revealed taken out. The solver, the bot strategies
+//! and the network games only ever see a PlayerView.
+use crate::cell::Cell;
+use crate::{Board, MinesweeperSquare, Neighbourhood, SquareState, Topology};
+use std::fmt;
+
+/// The board as the player sees it: revealed numbers, revealed mines, flags, the shape of the
+/// board and the total number of mines
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::view::PlayerView;
+/// use rusty_mine_sweeper::{Board, MinesweeperSquare};
+/// let mut board: Board<MinesweeperSquare> = Board::isize_board(9, 9);
+/// board.place_mines_seeded(10, 4);
+/// let view = PlayerView::new(&board);
+///
+/// assert_eq!(view.mine_count(), 10);
+/// assert_eq!(view.hidden_cells().count(), 81);
+/// assert!(view.cells().all(|cell| cell.number().is_none()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerView {
+    /// Copy of the board with no mines or numbers left under unrevealed squares
+    board: Board<MinesweeperSquare>,
+    mine_count: usize,
+}
+
+impl PlayerView {
+    /// Takes what the player can see from the board
+    pub fn new(board: &Board<MinesweeperSquare>) -> PlayerView {
+        let mut visible = board.clone();
+        // the seed says where a mine under the first click would be moved to
+        visible.first_click = None;
+        for square in visible.board.iter_mut() {
+            if square.state != SquareState::Revealed {
+                square.mines = 0;
+                square.count = 0;
+                square.value = -1;
+            }
+        }
+        PlayerView {
+            board: visible,
+            mine_count: board.mine_count(),
+        }
+    }
+
+    /// Number of columns
+    pub fn width(&self) -> usize {
+        self.board.width
+    }
+
+    /// Number of rows
+    pub fn height(&self) -> usize {
+        self.board.height
+    }
+
+    /// Number of mines on the whole board, found or not
+    pub fn mine_count(&self) -> usize {
+        self.mine_count
+    }
+
+    /// Returns how the edges of the board behave
+    pub fn topology(&self) -> Topology {
+        self.board.topology()
+    }
+
+    /// Returns which squares count as neighbours on the board
+    pub fn neighbourhood(&self) -> Neighbourhood {
+        self.board.neighbourhood()
+    }
+
+    /// Returns the view of the square at the given cords, see Board::cell
+    pub fn cell(&self, x: usize, y: usize) -> Option<Cell> {
+        self.board.cell(x, y)
+    }
+
+    /// Iterates over every square row after row, see Board::cells
+    pub fn cells(&self) -> impl Iterator<Item = Cell> + '_ {
+        self.board.cells()
+    }
+
+    /// Iterates over the cords of the neighbours of (x, y), see Board::neighbours
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.board.neighbours(x, y)
+    }
+
+    /// Iterates over the neighbours of (x, y) as cells
+    pub fn neighbour_cells(&self, x: usize, y: usize) -> impl Iterator<Item = Cell> + '_ {
+        self.board.neighbour_cells(x, y)
+    }
+
+    /// Iterates over the squares that have not been revealed or flagged yet
+    pub fn hidden_cells(&self) -> impl Iterator<Item = Cell> + '_ {
+        self.board.hidden_cells()
+    }
+
+    /// Iterates over the squares with flags on them
+    pub fn flagged_cells(&self) -> impl Iterator<Item = Cell> + '_ {
+        self.board.flagged_cells()
+    }
+}
+
+impl From<&Board<MinesweeperSquare>> for PlayerView {
+    fn from(board: &Board<MinesweeperSquare>) -> PlayerView {
+        PlayerView::new(board)
+    }
+}
+
+/// Draws the board the same way as Board, which never shows a hidden mine either
+impl fmt::Display for PlayerView {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.board.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::{BoardBuilder, FirstClick};
+    use crate::cell::CellState;
+    use crate::layout;
+
+    #[test]
+    fn test_hidden_mines_are_left_out() {
+        let mut board = layout::parse("*..\n.F.\n..*").unwrap();
+        board.reveal(2, 0).unwrap();
+        let view = PlayerView::new(&board);
+        assert_eq!(view.mine_count(), 3);
+        assert_eq!(view.board.mine_count(), 0);
+        assert!(view.board.iter().flatten().all(|square| !square.is_mine()));
+        assert_eq!(view.cell(2, 0).unwrap().state, CellState::Revealed(1));
+        assert_eq!(view.cell(1, 1).unwrap().state, CellState::Flagged(1));
+        assert_eq!(view.to_string(), board.to_string());
+    }
+
+    #[test]
+    fn test_first_click_seed_is_left_out() {
+        let builder = BoardBuilder::new(9, 9)
+            .mines(10)
+            .first_click(FirstClick::Safe);
+        let first = builder.clone().seed(1).build().unwrap();
+        let mut second = first.clone();
+        second.first_click = Some((FirstClick::Safe, 2));
+        assert_ne!(first, second);
+        assert_eq!(PlayerView::new(&first), PlayerView::new(&second));
+        assert_eq!(
+            format!("{:?}", PlayerView::new(&first)),
+            format!("{:?}", PlayerView::new(&second))
+        );
+        assert!(!format!("{:?}", PlayerView::new(&first)).contains("Safe"));
+    }
+
+    #[test]
+    fn test_revealed_mines_stay_visible() {
+        let mut board = layout::parse("*.\n..").unwrap();
+        board.reveal(0, 0).unwrap();
+        let view = PlayerView::from(&board);
+        assert_eq!(view.cell(0, 0).unwrap().state, CellState::Mine(1));
+        assert_eq!(view.hidden_cells().count(), 3);
+    }
+}
@@ -0,0 +1,264 @@
+use crate::api;
+use crate::cell::CellState;
+use crate::http::{self, Request, Response};
+use crate::report::{ClickCounts, GameReport};
+use crate::session::SessionManager;
+use crate::view::PlayerView;
+use crate::{Board, MinesweeperSquare, Preset, RevealOutcome};
+use std::io;
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// The game being played through the web page
+pub struct WebGame {
+    board: Board<MinesweeperSquare>,
+    preset: Preset,
+    seed: u64,
+    clicks: ClickCounts,
+    started: Option<Instant>,
+    finished: Option<GameReport>,
+    message: Option<&'static str>,
+}
+
+impl WebGame {
+    /// Starts a new game on the preset, placing the mines from the seed
+    pub fn new(preset: Preset, seed: u64) -> WebGame {
+        WebGame {
+            board: preset.board_with_seed(seed),
+            preset,
+            seed,
+            clicks: ClickCounts::default(),
+            started: None,
+            finished: None,
+            message: None,
+        }
+    }
+
+    /// Applies a reveal, flag or chord move on the square at (x, y)
+    fn make_move(&mut self, mode: &str, x: usize, y: usize) {
+        if self.finished.is_some() {
+            self.message = Some("The game is over. Start a new game to keep playing");
+            return;
+        }
+        let outcome = match mode {
+            "flag" => self.board.toggle_flag(x, y).map(|_| {
+                self.clicks.flags += 1;
+                RevealOutcome::Revealed(0)
+            }),
+            "chord" => self.board.chord(x, y).inspect(|_| self.clicks.chords += 1),
+            _ => self
+                .board
+                .reveal(x, y)
+                .inspect(|_| self.clicks.reveals += 1),
+        };
+        if outcome.is_ok() {
+            // a move that was turned down does not start the clock
+            self.started.get_or_insert_with(Instant::now);
+        }
+        match outcome {
+            Ok(RevealOutcome::Mine) => self.finish(),
+            Ok(RevealOutcome::Revealed(_)) => {
+                self.message = None;
+                if self.board.is_won().is_some() {
+                    self.finish();
+                }
+            }
+            Err(e) => self.message = Some(e),
+        }
+    }
+
+    /// Records the end of game report
+    fn finish(&mut self) {
+        let elapsed = self
+            .started
+            .map(|start| start.elapsed())
+            .unwrap_or_default();
+        self.finished = Some(GameReport::new(&self.board, self.clicks, elapsed));
+    }
+
+    /// Renders the whole page for the game
+    fn render(&self) -> String {
+        let mut page = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Rusty Mine Sweeper</title>\n<style>\n\
+             table { border-collapse: collapse; }\n\
+             td { width: 2em; height: 2em; text-align: center; border: 1px solid #888; padding: 0; }\n\
+             td button { width: 100%; height: 100%; }\n\
+             td.void { border: none; }\n\
+             </style>\n</head>\n<body>\n<h1>Rusty Mine Sweeper</h1>\n",
+        );
+        page.push_str(&format!(
+            "<p>{} board, seed {}, {} mines</p>\n",
+            self.preset.name(),
+            self.seed,
+            self.board.mine_count()
+        ));
+        if let Some(message) = self.message {
+            page.push_str(&format!("<p class=\"message\">{}</p>\n", escape(message)));
+        }
+        if let Some(report) = &self.finished {
+            let result = if report.won { "You won!" } else { "You lose" };
+            page.push_str(&format!(
+                "<h2 class=\"result\">{}</h2>\n<pre>{}</pre>\n",
+                result,
+                escape(&report.to_string())
+            ));
+        }
+
+        page.push_str("<form method=\"post\" action=\"/move\">\n<p>\n");
+        for (mode, label) in [("reveal", "Reveal"), ("flag", "Flag"), ("chord", "Chord")] {
+            page.push_str(&format!(
+                "<label><input type=\"radio\" name=\"mode\" value=\"{}\"{}> {}</label>\n",
+                mode,
+                if mode == "reveal" { " checked" } else { "" },
+                label
+            ));
+        }
+        page.push_str("</p>\n<table>\n");
+        let disabled = if self.finished.is_some() {
+            " disabled"
+        } else {
+            ""
+        };
+        let view = PlayerView::new(&self.board);
+        for y in 0..view.height() {
+            page.push_str("<tr>");
+            for cell in (0..view.width()).filter_map(|x| view.cell(x, y)) {
+                let label = match cell.state {
+                    CellState::Hidden => String::from("&nbsp;"),
+                    CellState::Flagged(_) => String::from("&#x1F6A9;"),
+                    CellState::Mine(_) => String::from("*"),
+                    CellState::Revealed(0) => {
+                        page.push_str("<td class=\"empty\"></td>");
+                        continue;
+                    }
+                    CellState::Void => {
+                        page.push_str("<td class=\"void\"></td>");
+                        continue;
+                    }
+                    CellState::Revealed(number) => number.to_string(),
+                };
+                page.push_str(&format!(
+                    "<td><button name=\"cell\" value=\"{},{}\"{}>{}</button></td>",
+                    cell.x, cell.y, disabled, label
+                ));
+            }
+            page.push_str("</tr>\n");
+        }
+        page.push_str("</table>\n</form>\n");
+
+        page.push_str(
+            "<form method=\"post\" action=\"/new\">\n<p>New game: <select name=\"preset\">",
+        );
+        for preset in Preset::ALL {
+            page.push_str(&format!(
+                "<option value=\"{}\"{}>{}</option>",
+                preset.name(),
+                if preset == self.preset {
+                    " selected"
+                } else {
+                    ""
+                },
+                preset.name()
+            ));
+        }
+        page.push_str(
+            "</select> seed <input name=\"seed\" size=\"20\" placeholder=\"random\"> \
+             <button>Start</button></p>\n</form>\n</body>\n</html>\n",
+        );
+        page
+    }
+}
+
+/// Escapes the characters that have special meaning in HTML
+/// # Examples
+/// ```
+/// use rusty_mine_sweeper::web::escape;
+///
+/// assert_eq!(escape("<b>&\"</b>"), "&lt;b&gt;&amp;&quot;&lt;/b&gt;");
+/// ```
+pub fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Parses a "x,y" cell value from the board form
+fn parse_cell(value: &str) -> Option<(usize, usize)> {
+    let (x, y) = value.split_once(',')?;
+    Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+/// Handles a single request against the game.
+/// GET / shows the board, POST /move makes a move and POST /new starts a new game
+pub fn handle(game: &Mutex<WebGame>, request: &Request) -> Response {
+    let mut game = match game.lock() {
+        Ok(game) => game,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/") => Response::html(game.render()),
+        ("POST", "/move") => {
+            let form = request.form();
+            let Some((x, y)) = form.get("cell").and_then(|cell| parse_cell(cell)) else {
+                return Response::text(400, "Missing or invalid cell");
+            };
+            let mode = form.get("mode").map_or("reveal", String::as_str);
+            game.make_move(mode, x, y);
+            Response::redirect("/")
+        }
+        ("POST", "/new") => {
+            let form = request.form();
+            let preset = form
+                .get("preset")
+                .and_then(|name| Preset::from_name(name))
+                .unwrap_or(game.preset);
+            let seed = form
+                .get("seed")
+                .and_then(|seed| seed.trim().parse().ok())
+                .unwrap_or_else(rand::random);
+            *game = WebGame::new(preset, seed);
+            Response::redirect("/")
+        }
+        (_, "/") | (_, "/move") | (_, "/new") => Response::text(405, "Method not allowed"),
+        _ => Response::text(404, "Not found"),
+    }
+}
+
+/// Serves the HTML board on the listener until the process exits.
+/// Requests under /api/ are passed on to the JSON API
+pub fn serve(listener: TcpListener, preset: Preset) -> io::Result<()> {
+    let game = Arc::new(Mutex::new(WebGame::new(preset, rand::random())));
+    let sessions = Arc::new(SessionManager::default());
+    http::serve(listener, move |request| {
+        if request.path.starts_with("/api/") {
+            api::handle(&sessions, request)
+        } else {
+            handle(&game, request)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cell() {
+        assert_eq!(parse_cell("3,4"), Some((3, 4)));
+        assert_eq!(parse_cell("3"), None);
+        assert_eq!(parse_cell("a,4"), None);
+    }
+
+    #[test]
+    fn test_rejected_moves_do_not_start_the_clock() {
+        let mut game = WebGame::new(Preset::Beginner, 3);
+        game.make_move("reveal", 100, 0);
+        game.make_move("chord", 0, 0);
+        assert!(game.message.is_some());
+        assert!(game.started.is_none());
+        game.make_move("flag", 0, 0);
+        assert!(game.started.is_some());
+    }
+}
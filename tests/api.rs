@@ -0,0 +1,94 @@
+use rusty_mine_sweeper::json::Json;
+use rusty_mine_sweeper::web;
+use rusty_mine_sweeper::Preset;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+
+/// Starts the server on a free localhost port and returns its address
+fn start_server() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || web::serve(listener, Preset::Beginner));
+    addr
+}
+
+/// Sends a JSON request and returns the status code and the parsed body
+fn send(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Option<Json>) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response[9..12].parse().unwrap();
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    (status, Json::parse(body).ok())
+}
+
+#[test]
+fn test_play_a_game_over_the_api() {
+    let addr = start_server();
+    let (status, state) = send(
+        addr,
+        "POST",
+        "/api/games",
+        r#"{"width":8,"height":8,"mines":10,"seed":99}"#,
+    );
+    assert_eq!(status, 201);
+    let state = state.unwrap();
+    let id = state.get("id").and_then(Json::as_str).unwrap().to_string();
+    assert_eq!(state.get("status").and_then(Json::as_str), Some("playing"));
+
+    let mut board = rusty_mine_sweeper::Board::isize_board(8, 8);
+    board.place_mines_seeded(10, 99);
+    let (x, y) = (0..64)
+        .map(|i| (i % 8, i / 8))
+        .find(|&(x, y)| !board.get(x, y).unwrap().get_is_mine())
+        .unwrap();
+
+    let path = format!("/api/games/{}/reveal", id);
+    let (status, state) = send(addr, "POST", &path, &format!(r#"{{"x":{},"y":{}}}"#, x, y));
+    assert_eq!(status, 200);
+    let state = state.unwrap();
+    let Some(Json::Array(rows)) = state.get("cells") else {
+        panic!("cells should be an array of rows");
+    };
+    assert_eq!(rows.len(), 8);
+    assert!(!rows[y].to_string().contains("\"mine\""));
+
+    let (status, _) = send(addr, "POST", &path, &format!(r#"{{"x":{},"y":{}}}"#, x, y));
+    assert_eq!(status, 409);
+
+    let game = format!("/api/games/{}", id);
+    assert_eq!(send(addr, "GET", &game, "").0, 200);
+    assert_eq!(send(addr, "DELETE", &game, "").0, 204);
+    assert_eq!(send(addr, "GET", &game, "").0, 404);
+}
+
+#[test]
+fn test_many_concurrent_games() {
+    let addr = start_server();
+    let ids: Vec<String> = (0..5)
+        .map(|seed| {
+            let body = format!(r#"{{"preset":"beginner","seed":{}}}"#, seed);
+            let (_, state) = send(addr, "POST", "/api/games", &body);
+            state
+                .unwrap()
+                .get("id")
+                .and_then(Json::as_str)
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    for id in ids.iter() {
+        let (status, _) = send(addr, "GET", &format!("/api/games/{}", id), "");
+        assert_eq!(status, 200);
+    }
+}
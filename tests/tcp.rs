@@ -0,0 +1,111 @@
+use rusty_mine_sweeper::{race, tcp, Preset};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+/// Starts the server on a free localhost port with the given idle timeout and returns its address
+fn start_server(idle_timeout: Duration) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || tcp::serve(listener, idle_timeout));
+    addr
+}
+
+/// Reads from the connection until the text has been seen, returning everything read
+fn read_until(reader: &mut impl BufRead, text: &str) -> String {
+    let mut seen = String::new();
+    while !seen.contains(text) {
+        let buffer = reader.fill_buf().unwrap();
+        assert!(
+            !buffer.is_empty(),
+            "connection closed before {:?} in {:?}",
+            text,
+            seen
+        );
+        seen.push_str(&String::from_utf8_lossy(buffer));
+        let read = buffer.len();
+        reader.consume(read);
+    }
+    seen
+}
+
+#[test]
+fn test_two_players_get_their_own_games() {
+    let addr = start_server(Duration::from_secs(10));
+    let mut players: Vec<(TcpStream, BufReader<TcpStream>)> = (0..2)
+        .map(|_| {
+            let stream = TcpStream::connect(addr).unwrap();
+            let reader = BufReader::new(stream.try_clone().unwrap());
+            (stream, reader)
+        })
+        .collect();
+
+    for (stream, reader) in players.iter_mut() {
+        read_until(reader, "4. Custom");
+        stream.write_all(b"1\n").unwrap();
+        let board = read_until(reader, "3. Chord on a number");
+        assert!(board.contains("a "));
+        stream.write_all(b"1\n").unwrap();
+        read_until(reader, "(must be char): ");
+        stream.write_all(b"a\n").unwrap();
+        read_until(reader, "(must be num): ");
+        stream.write_all(b"1\n").unwrap();
+        read_until(reader, "Board after your mark/flag:");
+    }
+}
+
+#[test]
+fn test_idle_players_are_disconnected() {
+    let addr = start_server(Duration::from_millis(200));
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let mut output = String::new();
+    stream.read_to_string(&mut output).unwrap();
+    assert!(output.contains("Disconnected after being idle for too long"));
+}
+
+#[test]
+fn test_race_sees_opponent_progress_and_survivor_wins() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let host = thread::spawn(move || {
+        race::host(&listener, Preset::Beginner, 7, Duration::from_secs(10)).unwrap()
+    });
+    let connect = || {
+        let stream = TcpStream::connect(addr).unwrap();
+        let reader = BufReader::new(stream.try_clone().unwrap());
+        (stream, reader)
+    };
+    let (mut first, mut first_reader) = connect();
+    read_until(&mut first_reader, "Waiting for an opponent");
+    let (mut second, mut second_reader) = connect();
+
+    // both players get the same board, so a square found on one is the same for the other
+    let board = Preset::Beginner.board_with_seed(7);
+    let square = |mine: bool| {
+        (0..81)
+            .map(|i| (i % 9, i / 9))
+            .find(|&(x, y)| board.get(x, y).unwrap().get_is_mine() == mine)
+            .unwrap()
+    };
+    let select = |stream: &mut TcpStream, (x, y)| {
+        let row = (b'a' + y as u8) as char;
+        write!(stream, "2\n{}\n{}\n", row, x + 1).unwrap();
+    };
+
+    read_until(&mut first_reader, "3. Chord on a number");
+    select(&mut first, square(false));
+    // the second player's menu and the news of the first player's move come from different
+    // threads, so either can arrive first
+    let mut seen = read_until(&mut second_reader, "Your opponent has cleared");
+    while !seen.contains("3. Chord on a number") {
+        seen.push_str(&read_until(&mut second_reader, "\n"));
+    }
+    select(&mut second, square(true));
+    read_until(&mut first_reader, "You win the race!");
+    read_until(&mut second_reader, "You lose the race.");
+    assert_eq!(host.join().unwrap(), 0);
+}
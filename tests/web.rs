@@ -0,0 +1,90 @@
+use rusty_mine_sweeper::web;
+use rusty_mine_sweeper::Preset;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread;
+
+/// Starts the web server on a free localhost port and returns its address
+fn start_server() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || web::serve(listener, Preset::Beginner));
+    addr
+}
+
+/// Sends a raw request and returns the status code and the whole response
+fn send(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response[9..12].parse().unwrap();
+    (status, response)
+}
+
+#[test]
+fn test_board_is_rendered_without_javascript() {
+    let addr = start_server();
+    let (status, page) = send(addr, "GET", "/", "");
+    assert_eq!(status, 200);
+    assert!(page.contains("<form method=\"post\" action=\"/move\">"));
+    assert_eq!(page.matches("name=\"cell\"").count(), 81);
+    assert!(!page.contains("<script"));
+}
+
+#[test]
+fn test_moves_update_the_board() {
+    let addr = start_server();
+    let (status, response) = send(addr, "POST", "/new", "preset=beginner&seed=42");
+    assert_eq!(status, 303);
+    assert!(response.contains("Location: /"));
+
+    let mut expected = Preset::Beginner.board_with_seed(42);
+    // find a square without a mine so the reveal can not end the game
+    let (x, y) = (0..81)
+        .map(|i| (i % 9, i / 9))
+        .find(|&(x, y)| !expected.get(x, y).unwrap().get_is_mine())
+        .unwrap();
+    expected.reveal(x, y).unwrap();
+
+    let (status, _) = send(
+        addr,
+        "POST",
+        "/move",
+        &format!("mode=reveal&cell={}%2C{}", x, y),
+    );
+    assert_eq!(status, 303);
+    let (_, page) = send(addr, "GET", "/", "");
+    let hidden = expected.to_string().matches('\u{25FB}').count();
+    assert_eq!(page.matches("&nbsp;</button>").count(), hidden);
+
+    // flag a square that is still hidden and check it is shown as flagged
+    let (fx, fy) = (0..81)
+        .map(|i| (i % 9, i / 9))
+        .find(|&(fx, fy)| expected.toggle_flag(fx, fy).is_ok())
+        .unwrap();
+    send(
+        addr,
+        "POST",
+        "/move",
+        &format!("mode=flag&cell={}%2C{}", fx, fy),
+    );
+    let (_, page) = send(addr, "GET", "/", "");
+    assert!(page.contains(&format!("value=\"{},{}\">&#x1F6A9;</button>", fx, fy)));
+}
+
+#[test]
+fn test_bad_requests() {
+    let addr = start_server();
+    assert_eq!(send(addr, "GET", "/missing", "").0, 404);
+    assert_eq!(send(addr, "GET", "/move", "").0, 405);
+    assert_eq!(send(addr, "POST", "/move", "cell=nope").0, 400);
+}